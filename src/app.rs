@@ -1,130 +1,546 @@
-use std::collections::HashMap;
 use std::env;
 
 use std::io::ErrorKind;
-use std::net::UdpSocket;
-use rand::distributions::Alphanumeric;
-use rand::Rng;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
+use sdl2::clipboard::ClipboardUtil;
 use sdl2::pixels::Color;
-use sdl2::render::TextureCreator;
-use sdl2::video::WindowContext;
-use sdl2::{video::Window, Sdl, render::Canvas};
-use serde::{Deserialize, Serialize};
+use sdl2::surface::Surface;
+use sdl2::{video::{FullscreenType, Window}, Sdl, render::Canvas};
+use crate::engine::assets::Assets;
+use crate::engine::audio::Audio;
+use crate::engine::display::{self, WindowMode};
+use crate::engine::log::{self, LogSettings};
+use crate::engine::screenshot;
+use crate::engine::settings::{self, ClientSettings, VideoSettings};
+use crate::engine::sprite::SpriteConfig;
 use crate::engine::time::Timing;
+use crate::gameplay::lobby::{ConnectScreen, DisconnectedScreen, Lobby};
+use crate::gameplay::menu::{ControlsMenu, MainMenu, SettingsMenu};
 use crate::gameplay::play;
+use crate::gameplay::map::MapData;
+use crate::gameplay::scene::Scene;
 use crate::gameplay::server_game_logic::Returnable;
+use crate::input::bindings::Bindings;
+use crate::input::gamepad::Gamepads;
+use crate::networking::error::{self, NetworkError};
+use crate::networking::protocol::{ChatMessage, ClientMessage, ServerMessage};
+use crate::networking::reliable::{ReliableReceiver, ReliableSender};
+use crate::networking::server::{HostedServer, Server};
+use crate::networking::stats::NetStats;
+use crate::ui::net_overlay::NetOverlay;
+use crate::ui::text_cache::TextCache;
 
+#[derive(Clone, Copy, PartialEq)]
 pub enum GameState {
+    Menu,
+    Settings,
+    Controls,
+    Connecting,
+    Lobby,
     Playing,
+    Disconnected,
 }
 
 pub struct AppState {
     pub is_running: bool,
     pub state: GameState,
+    /// Reason shown on the disconnected screen.
+    pub disconnect_reason: String,
+}
+
+impl AppState {
+    pub fn disconnected(&mut self, reason: &str) {
+        self.state = GameState::Disconnected;
+        self.disconnect_reason = reason.to_string();
+    }
 }
 
 pub struct ConnectionData {
     pub socket: UdpSocket,
-    pub connect_to: String
+    /// Address written by the player, it's the one shown on the screens.
+    pub connect_to: String,
+    /// Address the server was found on, the datagrams from any other address are ignored.
+    pub server: SocketAddr,
+    pub last_received: Instant,
+    pub last_sent: Instant,
+    /// Receives that failed in a row, after [`App::MAX_RECEIVE_FAILURES`] the connection is closed.
+    pub receive_failures: u32,
 }
 
 pub struct App {
     pub context: Sdl,
-    pub width: u32,
-    pub height: u32,
     pub canvas: Canvas<Window>,
-    pub texture_creator: TextureCreator<WindowContext>,
-    pub socket: UdpSocket,
-    pub connect_to: String,
+    pub clipboard: ClipboardUtil,
+    pub audio: Audio,
+    /// The server we are connected to, `None` while we are on the menus.
+    pub connection: Option<ConnectionData>,
+    /// The server started with the "Host" button of the menu.
+    pub hosted_server: Option<HostedServer>,
+    /// Id of our player on the snapshots, the server gives it on the `ServerMessage::Welcome`.
+    pub instance_id: String,
     pub received: Option<Returnable>,
+    pub map: Option<MapData>,
+    /// Name asked by the player, once the server welcomes us it's replaced by the one the server accepted.
+    pub player_name: String,
+    /// Name written on the connect screen, it's the one saved on the settings (the server can change `player_name` to keep it unique).
+    pub preferred_name: String,
+    /// Address written the last time on the connect screen.
+    pub last_server: String,
+    pub chat_in: ReliableReceiver<ChatMessage>,
+    pub chat_out: ReliableSender<String>,
+    /// Chat messages received in order and not yet shown by the gameplay.
+    pub chat_inbox: Vec<ChatMessage>,
+    /// Counters of the connection, they start again with each connection.
+    pub stats: NetStats,
+    /// Video settings as they are now, the window follows them and `width`/`height` follow a window resized by hand.
+    pub video: VideoSettings,
+    /// Keys of each action of the gameplay, they are changed from the controls screen.
+    pub bindings: Bindings,
+    /// The log can't change while the game runs, they are kept to save them as they were.
+    pub log: LogSettings,
+    pub gamepads: Gamepads,
+    /// The network stats overlay of the match, it's kept between matches.
+    pub net_overlay: NetOverlay,
+    /// Set by the gameplay when the screenshot key is pressed, the frame is saved before it's shown.
+    pub screenshot_requested: bool,
     pub time: Timing
 }
 
 impl App {
-    pub fn new(title: &str, socket: UdpSocket, connect_to: String) -> App{
+    // time without any data from the server before we consider the connection lost
+    const TIMEOUT: Duration = Duration::from_secs(5);
+    // if nothing was sent on this time a keep alive is sent, so the server doesn't drop us
+    const KEEP_ALIVE: Duration = Duration::from_secs(1);
+    // receive errors in a row before giving up, a reset from a single lost datagram doesn't count
+    const MAX_RECEIVE_FAILURES: u32 = 5;
+    const BACKGROUND: Color = Color::RGBA(40, 40, 40, 100);
+
+    pub fn new(title: &str) -> App{
+        // without settings the game still starts, with the default ones
+        // the defaults are saved over the file on the next change, so the one that failed is kept aside first
+        let (settings, error) = match ClientSettings::load(ClientSettings::DEFAULT_PATH) {
+            Ok(settings) => (settings, None),
+            Err(err) => (ClientSettings::default(), Some((err, settings::back_up(ClientSettings::DEFAULT_PATH)))),
+        };
+
+        // the log needs the settings, so the error is written once it's ready
+        log::init(&settings.log);
+        match error {
+            Some((err, Ok(Some(backup)))) => log::warn!("{}, the default settings are used and the file was kept as {}", err, backup.display()),
+            Some((err, Ok(None))) => log::warn!("{}, the default settings are used", err),
+            Some((err, Err(backup_err))) => log::warn!("{}, the default settings are used ({})", err, backup_err),
+            None => {},
+        }
+
+        Self::with_settings(title, settings)
+    }
+
+    /// # Headless
+    /// Creates the app without a display, SDL draws on memory with the software renderer so it works on machines without a screen or a GPU.
+    ///
+    /// The settings file is not read, so the frames only depend on the scene that is drawn (see [`App::render_scene`]).
+    pub fn headless(width: u32, height: u32) -> App {
+        env::set_var("SDL_VIDEODRIVER", "dummy");
+        env::set_var("SDL_AUDIODRIVER", "dummy");
+
+        let video = VideoSettings {
+            width,
+            height,
+            mode: WindowMode::Windowed,
+            vsync: false,
+            frame_cap: 0,
+            renderer: String::from("software"),
+            // the framerate changes between runs, so it's hidden
+            show_framerate: false,
+        };
+
+        Self::with_settings("Arrownier", ClientSettings { video, ..ClientSettings::default() })
+    }
+
+    fn with_settings(title: &str, settings: ClientSettings) -> App {
         // base sdl2
         let context = sdl2::init().expect("SDL2 wasn't initialized");
         let video_susbsystem = context.video().expect("The Video subsystem wasn't initialized");
 
-        let current_display = video_susbsystem.current_display_mode(0).unwrap();
-        
-        let width = current_display.w as u32;
-        let height = current_display.h as u32;
-
         env::set_var("SDL_VIDEO_MINIMIZE_ON_FOCUS_LOSS", "0");
 
-        let window = video_susbsystem.window(title, 1280, 720 as u32).vulkan().build().expect("The window wasn't created");
-        let mut canvas = window.into_canvas().accelerated().present_vsync().build().expect("the canvas wasn't builded");
-        
+        let mut canvas = display::create_canvas(&video_susbsystem, title, &settings.video).expect("The window wasn't created");
+
         canvas.set_blend_mode(sdl2::render::BlendMode::Blend);
-        let texture_creator = canvas.texture_creator();
 
+        let clipboard = video_susbsystem.clipboard();
+        let mut audio = Audio::new(&context);
+        audio.set_volumes(settings.audio);
+        let gamepads = Gamepads::new(&context);
         let time = Timing::new();
 
         App {
             context,
-            width,
-            height,
             canvas,
-            texture_creator,
-            socket,
-            connect_to,
+            clipboard,
+            audio,
+            connection: None,
+            hosted_server: None,
+            instance_id: String::new(),
             received: None,
+            map: None,
+            player_name: settings.player_name(),
+            preferred_name: settings.player_name(),
+            last_server: settings.last_server.clone(),
+            chat_in: ReliableReceiver::new(),
+            chat_out: ReliableSender::new(),
+            chat_inbox: Vec::new(),
+            stats: NetStats::new(),
+            video: settings.video.clone(),
+            bindings: settings.controls,
+            log: settings.log,
+            gamepads,
+            net_overlay: NetOverlay::new(),
+            screenshot_requested: false,
             time
         }
     }
 
-    pub fn render(mut self) {
-        let mut app_state = AppState { is_running: true, state: GameState::Playing };
-        let mut event_pump = self.context.event_pump().unwrap();
+    /// The settings as they are now.
+    pub fn settings(&self) -> ClientSettings {
+        ClientSettings {
+            video: self.video.clone(),
+            audio: self.audio.volumes(),
+            controls: self.bindings.clone(),
+            last_server: self.last_server.clone(),
+            name: self.preferred_name.clone(),
+            log: self.log.clone(),
+        }
+    }
 
-        let ttf_context = sdl2::ttf::init().unwrap(); // we create a "context"
-        let use_font = "./assets/fonts/Inter-Thin.ttf";
-        let mut _font = ttf_context.load_font(use_font, 20).unwrap();
+    /// Writes the settings file, it's called each time a setting changes so nothing is lost if the game is closed abruptly.
+    pub fn save_settings(&self) -> Result<(), String> {
+        self.settings().save(ClientSettings::DEFAULT_PATH)
+    }
 
-        let mut play = play::GameLogic::new(&mut self);
+    /// # Apply Video
+    /// Changes the window to the mode and resolution of `video`, the errors are returned so the settings menu can show them.
+    pub fn apply_video(&mut self) -> Result<(), String> {
+        display::apply_mode(&mut self.canvas, &self.video)
+    }
 
-        // networking
-        let mut buf = [0; 1024];
+    // the window can be resized by hand, the new size is kept so it's the same the next time the game starts
+    fn track_window_size(&mut self) {
+        if self.video.mode != WindowMode::Windowed || self.canvas.window().fullscreen_state() != FullscreenType::Off {
+            return;
+        }
 
-        while app_state.is_running {
-            self.time.update();
-            self.canvas.set_draw_color(Color::RGBA(40, 40, 40, 100));
-            self.canvas.clear();
+        let (width, height) = self.canvas.window().size();
+        self.video.width = width.max(ClientSettings::MIN_WIDTH);
+        self.video.height = height.max(ClientSettings::MIN_HEIGHT);
+    }
+
+    /// # Connect
+    /// Opens a socket to talk with the server on `address`, the join itself is sent by the connect screen.
+    pub fn connect(&mut self, address: &str, name: &str) -> Result<(), String> {
+        let address = address.trim();
+
+        let server = match address.to_socket_addrs() {
+            Ok(mut addresses) => match addresses.next() {
+                Some(server) => server,
+                None => return Err(format!("The address {} was not found", address)),
+            },
+            Err(err) => return Err(format!("The address {} is not valid: {}", address, err)),
+        };
 
-            match self.socket.recv_from(&mut buf) {
+        log::info!("Connecting to {} as {}", address, name.trim());
+        let socket = UdpSocket::bind("0.0.0.0:0").map_err(|err| format!("Failed to create socket: {}", err))?;
+        socket.set_nonblocking(true).map_err(|err| format!("Failed to set non-blocking mode: {}", err))?;
+
+        self.reset_session();
+        self.player_name = name.trim().to_string();
+        self.instance_id = String::new();
+        self.connection = Some(ConnectionData {
+            socket,
+            connect_to: address.to_string(),
+            server,
+            last_received: Instant::now(),
+            last_sent: Instant::now(),
+            receive_failures: 0,
+        });
+
+        Ok(())
+    }
+
+    /// Starts a server on this same game and connects to it.
+    pub fn host(&mut self, name: &str) -> Result<(), String> {
+        let hosted_server = Server::new().host()?;
+        let address = format!("127.0.0.1:{}", hosted_server.address.port());
+
+        self.hosted_server = Some(hosted_server);
+        self.connect(&address, name)
+    }
+
+    /// Leaves the server, if we were hosting it the server is stopped too.
+    pub fn disconnect(&mut self) {
+        if let Some(connection) = &self.connection {
+            log::info!("Disconnected from {}", connection.connect_to);
+            self.send(&ClientMessage::Leave);
+        }
+
+        self.connection = None;
+        self.hosted_server = None;
+        self.reset_session();
+    }
+
+    pub fn send(&mut self, message: &ClientMessage) {
+        if let Some(connection) = &mut self.connection {
+            // a failed send is not a lost connection yet, the timeout decides it
+            match error::send_to(&connection.socket, message, connection.server) {
+                Ok(sent) => self.stats.sent(sent),
+                Err(err) => log::error!("{}", err),
+            }
+            connection.last_sent = Instant::now();
+        }
+    }
+
+    fn reset_session(&mut self) {
+        self.received = None;
+        self.map = None;
+        self.chat_in = ReliableReceiver::new();
+        self.chat_out = ReliableSender::new();
+        self.chat_inbox.clear();
+        self.stats = NetStats::new();
+    }
+
+    // read every message that arrived since the last frame
+    fn receive(&mut self, buf: &mut [u8], app_state: &mut AppState) {
+        loop {
+            let connection = match &mut self.connection {
+                Some(connection) => connection,
+                None => return,
+            };
+
+            match connection.socket.recv_from(buf) {
                 Ok((amt, src)) => {
-                    // println!("Received {} bytes from {}", amt, src);
-                    let received = std::str::from_utf8(&buf[..amt]).unwrap();
-                    let deserialized_option: Result<Returnable, serde_json::Error> = serde_json::from_str(&received);
-                    
-                    match deserialized_option {
-                        Ok(deserialized) => {
+                    // only the server can talk to us, anything else could be someone faking its messages
+                    if src != connection.server {
+                        log::debug!("{} bytes from {} were ignored, it's not the server", amt, src);
+                        continue;
+                    }
+                    connection.last_received = Instant::now();
+                    connection.receive_failures = 0;
+                    self.stats.received(amt);
+
+                    match error::decode(&buf[..amt], src) {
+                        Ok(ServerMessage::Snapshot(deserialized)) => {
+                            self.stats.snapshot();
                             self.received = Some(deserialized)
                         },
+                        Ok(ServerMessage::Welcome { map, name, id }) => {
+                            log::info!("Joined the server as {} on the map {}", name, map.name);
+                            self.map = Some(map);
+                            self.player_name = name;
+                            self.instance_id = id;
+                        },
+                        Ok(ServerMessage::Chat { sequence, message }) => {
+                            // duplicates are acknowledged too, the first acknowledge could have been lost
+                            if let Some(ready) = self.chat_in.receive(sequence, message) {
+                                self.send(&ClientMessage::ChatAck { sequence });
+                                self.chat_inbox.extend(ready);
+                            }
+                        },
+                        Ok(ServerMessage::ChatAck { sequence }) => {
+                            self.chat_out.acknowledge(sequence);
+                        },
+                        Ok(ServerMessage::Pong { sequence, tick, input_depth }) => {
+                            self.stats.pong(sequence, tick, input_depth);
+                        },
+                        Ok(ServerMessage::Rejected { reason }) => {
+                            log::warn!("The server rejected us: {}", reason);
+                            self.disconnect();
+                            app_state.disconnected(&reason);
+                            return;
+                        },
                         Err(err) => {
-                            eprintln!("Something went wrong when deserializing the received data: {}", err);
+                            log::warn!("{}", err);
                         },
                     }
                 },
                 Err(ref err) if err.kind() == ErrorKind::WouldBlock => {
                     // No data available at the moment, try again later
+                    break;
                 },
                 Err(err) => {
-                    // Handle other errors
-                    println!("Error: {}", err);
-                    break;
+                    // a reset comes from one datagram the server didn't get (like while it restarts), the timeout decides if it's gone
+                    let err = NetworkError::Receive(err);
+                    if err.is_connection_reset() {
+                        log::debug!("{}", err);
+                        continue;
+                    }
+
+                    connection.receive_failures += 1;
+                    if connection.receive_failures < Self::MAX_RECEIVE_FAILURES {
+                        log::warn!("{}", err);
+                        break;
+                    }
+
+                    log::error!("{}, the connection is closed", err);
+                    self.disconnect();
+                    app_state.disconnected(&err.to_string());
+                    return;
                 }
             }
-    
+        }
+
+        if let Some(connection) = &self.connection {
+            if connection.last_received.elapsed() >= Self::TIMEOUT {
+                let reason = if self.map.is_some() { "The connection with the server was lost" } else { "The server didn't answer" };
+                log::warn!("{}", reason);
+                self.disconnect();
+                app_state.disconnected(reason);
+            } else if let Some(sequence) = self.stats.ping_due() {
+                // the pings keep the connection alive too
+                self.send(&ClientMessage::Ping { sequence });
+            } else if connection.last_sent.elapsed() >= Self::KEEP_ALIVE {
+                self.send(&ClientMessage::KeepAlive);
+            }
+            self.stats.update();
+        }
+    }
+
+    pub fn render(mut self) {
+        let mut app_state = AppState { is_running: true, state: GameState::Menu, disconnect_reason: String::new() };
+        let mut event_pump = self.context.event_pump().unwrap();
+
+        let ttf_context = match sdl2::ttf::init() {
+            Ok(ttf_context) => ttf_context,
+            Err(err) => {
+                log::error!("SDL2_ttf wasn't initialized: {}", err);
+                return;
+            },
+        };
+
+        // the fonts and textures live as long as the texture creator, so it's created here instead of on the app
+        let texture_creator = self.canvas.texture_creator();
+        let mut assets = match Assets::new(&ttf_context, &texture_creator) {
+            Ok(assets) => assets,
+            Err(err) => {
+                log::error!("{}", err);
+                return;
+            },
+        };
+        // without sprites the game is still playable, everything is drawn with squares
+        if let Err(err) = assets.load_sprites(SpriteConfig::DEFAULT_PATH) {
+            log::warn!("{}, the missing sprites are drawn as squares", err);
+        }
+        // same with the sounds, the ones missing are not played
+        if let Err(err) = self.audio.load_sounds(&mut assets) {
+            log::warn!("{}", err);
+        }
+        if let Err(err) = self.audio.play_music(&mut assets, Audio::MUSIC) {
+            log::warn!("{}", err);
+        }
+        // the textures of the texts are kept between frames
+        let mut text_cache = TextCache::new(&texture_creator);
+
+        let mut main_menu = MainMenu::new();
+        let mut settings_menu = SettingsMenu::new(&mut self);
+        let mut controls_menu = ControlsMenu::new(&mut self);
+        let mut connect_screen = ConnectScreen::new(&mut self);
+        let mut lobby = Lobby::new();
+        let mut disconnected_screen = DisconnectedScreen::new();
+        // the gameplay is created each time we start playing, so nothing is left from the last game
+        let mut play: Option<play::GameLogic> = None;
+
+        // networking
+        let mut buf = [0; 65536];
+
+        while app_state.is_running {
+            self.time.update();
+            self.track_window_size();
+            self.canvas.set_draw_color(Self::BACKGROUND);
+            self.canvas.clear();
+
+            self.receive(&mut buf, &mut app_state);
+
             match app_state.state {
+                GameState::Menu => {
+                    main_menu.update(&assets, &mut text_cache, &mut app_state, &mut event_pump, &mut self);
+                },
+                GameState::Settings => {
+                    settings_menu.update(&assets, &mut text_cache, &mut app_state, &mut event_pump, &mut self);
+                },
+                GameState::Controls => {
+                    controls_menu.update(&assets, &mut text_cache, &mut app_state, &mut event_pump, &mut self);
+                },
+                GameState::Connecting => {
+                    connect_screen.update(&assets, &mut text_cache, &mut app_state, &mut event_pump, &mut self);
+                },
+                GameState::Lobby => {
+                    lobby.update(&assets, &mut text_cache, &mut app_state, &mut event_pump, &mut self);
+                },
                 GameState::Playing => {
-                    play.update(&_font, &mut app_state, &mut event_pump, &mut self);
+                    let play = play.get_or_insert_with(|| play::GameLogic::new(&mut self));
+                    play.update(&assets, &mut text_cache, &mut app_state, &mut event_pump, &mut self);
                 },
+                GameState::Disconnected => {
+                    disconnected_screen.update(&assets, &mut text_cache, &mut app_state, &mut event_pump, &mut self);
+                },
+            }
+
+            if app_state.state != GameState::Playing {
+                play = None;
             }
+
+            // the menus are only used without a server
+            if app_state.state == GameState::Menu && self.connection.is_some() {
+                self.disconnect();
+            }
+
+            // the frame is read before showing it, after the present the canvas has nothing
+            if std::mem::take(&mut self.screenshot_requested) {
+                match screenshot::take(&self.canvas) {
+                    Ok(path) => log::info!("Screenshot saved on {}", path.display()),
+                    Err(err) => log::error!("{}", err),
+                }
+            }
+
             self.canvas.present();
+            text_cache.end_frame();
+            self.time.limit(self.video.frame_cap);
+        }
+
+        self.disconnect();
+        if let Err(err) = self.save_settings() {
+            log::warn!("{}", err);
+        }
+    }
+
+    /// # Render Scene
+    /// Draws a world state with the same code of the gameplay, without a server, and returns the frame.
+    ///
+    /// The time doesn't move while drawing, so the same scene always gives the same pixels (the camera is centered on the local player and the animations are on their first frame).
+    pub fn render_scene(mut self, scene: &Scene) -> Result<Surface<'static>, String> {
+        let mut app_state = AppState { is_running: true, state: GameState::Playing, disconnect_reason: String::new() };
+        let mut event_pump = self.context.event_pump()?;
+        let ttf_context = sdl2::ttf::init().map_err(|err| format!("SDL2_ttf wasn't initialized: {}", err))?;
 
-            
+        let texture_creator = self.canvas.texture_creator();
+        let mut assets = Assets::new(&ttf_context, &texture_creator)?;
+        if let Err(err) = assets.load_sprites(SpriteConfig::DEFAULT_PATH) {
+            log::warn!("{}, the missing sprites are drawn as squares", err);
         }
+        let mut text_cache = TextCache::new(&texture_creator);
+
+        self.map = Some(MapData::load(&scene.map)?);
+        self.received = Some(scene.snapshot.clone());
+        self.instance_id = scene.local_player.clone();
+
+        // the gameplay creates the players on the first frame and draws them from the second one
+        let mut play = play::GameLogic::new(&mut self);
+        for _ in 0..2 {
+            self.canvas.set_draw_color(Self::BACKGROUND);
+            self.canvas.clear();
+            play.update(&assets, &mut text_cache, &mut app_state, &mut event_pump, &mut self);
+        }
+
+        screenshot::capture(&self.canvas)
     }
-}
\ No newline at end of file
+}
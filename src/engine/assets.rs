@@ -0,0 +1,180 @@
+use std::{cell::{RefCell, RefMut}, collections::HashMap, env, path::{Path, PathBuf}};
+
+use sdl2::{image::LoadTexture, render::{Texture, TextureCreator}, ttf::{Font, Sdl2TtfContext}, video::WindowContext};
+
+use super::sprite::{SpriteConfig, SpriteSheet};
+
+/// # Asset Path
+/// Finds a file of the assets folder, it's looked for next to the executable first (and on the folders above it, so `cargo run` finds the project assets from `target/debug`) and then on the working directory.
+///
+/// ## Returns:
+/// - The first path that exists, or the one next to the executable if the file is nowhere, so the error shows where it was expected.
+pub fn asset_path(relative: &str) -> PathBuf {
+    let relative = Path::new(relative.trim_start_matches("./"));
+    let mut candidates: Vec<PathBuf> = Vec::new();
+
+    if let Ok(executable) = env::current_exe() {
+        for folder in executable.ancestors().skip(1).take(4) {
+            candidates.push(folder.join(relative));
+        }
+    }
+    if let Ok(folder) = env::current_dir() {
+        candidates.push(folder.join(relative));
+    }
+
+    match candidates.iter().find(|candidate| candidate.exists()) {
+        Some(found) => found.clone(),
+        None => candidates.into_iter().next().unwrap_or_else(|| relative.to_path_buf()),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FontHandle(usize);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct TextureHandle(usize);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SoundHandle(usize);
+
+/// # Assets
+/// Loads the fonts, textures and sounds of the game once and gives a handle to use them, loading the same file again returns the same handle.
+///
+/// Every load returns a `Result` with a message that says which file failed and why, so a missing asset doesn't end on a panic.
+pub struct Assets<'ttf, 'tc> {
+    ttf_context: &'ttf Sdl2TtfContext,
+    texture_creator: &'tc TextureCreator<WindowContext>,
+    fonts: Vec<Font<'ttf, 'static>>,
+    font_handles: HashMap<(PathBuf, u16), FontHandle>,
+    // the textures are tinted while drawing, so they can be changed from a shared reference
+    textures: Vec<RefCell<Texture<'tc>>>,
+    texture_handles: HashMap<PathBuf, TextureHandle>,
+    sprite_sheets: HashMap<String, SpriteSheet>,
+    // the sounds are only found and checked here, the audio decides how to load them
+    sounds: Vec<PathBuf>,
+    sound_handles: HashMap<PathBuf, SoundHandle>,
+    text_font: FontHandle,
+    title_font: FontHandle,
+}
+
+impl<'ttf, 'tc> Assets<'ttf, 'tc> {
+    pub const TEXT_FONT: &'static str = "assets/fonts/Inter-Thin.ttf";
+    pub const TITLE_FONT: &'static str = "assets/fonts/8bitOperatorPlusSC-Regular.ttf";
+    const TEXT_SIZE: u16 = 20;
+    const TITLE_SIZE: u16 = 48;
+
+    /// Creates the manager with the fonts used by every screen already loaded.
+    pub fn new(ttf_context: &'ttf Sdl2TtfContext, texture_creator: &'tc TextureCreator<WindowContext>) -> Result<Self, String> {
+        let mut assets = Self {
+            ttf_context,
+            texture_creator,
+            fonts: Vec::new(),
+            font_handles: HashMap::new(),
+            textures: Vec::new(),
+            texture_handles: HashMap::new(),
+            sprite_sheets: HashMap::new(),
+            sounds: Vec::new(),
+            sound_handles: HashMap::new(),
+            text_font: FontHandle(0),
+            title_font: FontHandle(0),
+        };
+
+        assets.text_font = assets.load_font(Self::TEXT_FONT, Self::TEXT_SIZE)?;
+        assets.title_font = assets.load_font(Self::TITLE_FONT, Self::TITLE_SIZE)?;
+
+        Ok(assets)
+    }
+
+    /// Loads a font with a size in points, every size of the same file is a different font.
+    pub fn load_font(&mut self, path: &str, size: u16) -> Result<FontHandle, String> {
+        let path = asset_path(path);
+        if let Some(handle) = self.font_handles.get(&(path.clone(), size)) {
+            return Ok(*handle);
+        }
+
+        let font = self.ttf_context.load_font(&path, size).map_err(|err| format!("The font {} couldn't be loaded: {}", path.display(), err))?;
+        let handle = FontHandle(self.fonts.len());
+        self.fonts.push(font);
+        self.font_handles.insert((path, size), handle);
+
+        Ok(handle)
+    }
+
+    pub fn load_texture(&mut self, path: &str) -> Result<TextureHandle, String> {
+        let path = asset_path(path);
+        if let Some(handle) = self.texture_handles.get(&path) {
+            return Ok(*handle);
+        }
+
+        let texture = self.texture_creator.load_texture(&path).map_err(|err| format!("The texture {} couldn't be loaded: {}", path.display(), err))?;
+        let handle = TextureHandle(self.textures.len());
+        self.textures.push(RefCell::new(texture));
+        self.texture_handles.insert(path, handle);
+
+        Ok(handle)
+    }
+
+    pub fn load_sound(&mut self, path: &str) -> Result<SoundHandle, String> {
+        let path = asset_path(path);
+        if let Some(handle) = self.sound_handles.get(&path) {
+            return Ok(*handle);
+        }
+
+        if !path.is_file() {
+            return Err(format!("The sound {} was not found", path.display()));
+        }
+        let handle = SoundHandle(self.sounds.len());
+        self.sounds.push(path.clone());
+        self.sound_handles.insert(path, handle);
+
+        Ok(handle)
+    }
+
+    pub fn font(&self, handle: FontHandle) -> &Font<'ttf, 'static> {
+        &self.fonts[handle.0]
+    }
+
+    pub fn texture(&self, handle: TextureHandle) -> RefMut<'_, Texture<'tc>> {
+        self.textures[handle.0].borrow_mut()
+    }
+
+    /// # Load Sprites
+    /// Loads every sprite sheet of a sprite config, a sheet that fails doesn't stop the others.
+    ///
+    /// ## Returns:
+    /// - The errors of the sheets that couldn't be loaded, one per line.
+    pub fn load_sprites(&mut self, path: &str) -> Result<(), String> {
+        let config = SpriteConfig::load(path)?;
+        let mut errors = Vec::new();
+
+        for (name, sheet) in &config.sheets {
+            match SpriteSheet::load(self, sheet) {
+                Ok(sprite_sheet) => {
+                    self.sprite_sheets.insert(name.clone(), sprite_sheet);
+                },
+                Err(err) => errors.push(err),
+            }
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(errors.join("\n")) }
+    }
+
+    /// The sprite sheet with that name on the sprite config, `None` if it wasn't loaded.
+    pub fn sprite_sheet(&self, name: &str) -> Option<&SpriteSheet> {
+        self.sprite_sheets.get(name)
+    }
+
+    pub fn sound(&self, handle: SoundHandle) -> &Path {
+        &self.sounds[handle.0]
+    }
+
+    /// Font used by the buttons and texts of every screen.
+    pub fn text_font(&self) -> &Font<'ttf, 'static> {
+        self.font(self.text_font)
+    }
+
+    /// Bigger pixel font used by the titles.
+    pub fn title_font(&self) -> &Font<'ttf, 'static> {
+        self.font(self.title_font)
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use sdl2::{mixer::{self, Channel, Chunk, Music, Sdl2MixerContext}, AudioSubsystem, Sdl};
+use serde::{Deserialize, Serialize};
+
+use super::{assets::Assets, log};
+
+/// Sound effects of the game, each one is played when something happens on the match.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Sound {
+    /// A player fired an arrow.
+    Fire,
+    /// A player lost health.
+    Hit,
+    /// A player joined the match.
+    Join,
+}
+
+impl Sound {
+    pub const ALL: [Sound; 3] = [Sound::Fire, Sound::Hit, Sound::Join];
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            Sound::Fire => "assets/sounds/fire.wav",
+            Sound::Hit => "assets/sounds/hit.wav",
+            Sound::Join => "assets/sounds/join.wav",
+        }
+    }
+}
+
+/// Volume channels, the master volume multiplies the other two.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum VolumeChannel {
+    Master,
+    Sfx,
+    Music,
+}
+
+/// Volumes from 0 (silent) to 1 (full volume).
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct Volumes {
+    pub master: f32,
+    pub sfx: f32,
+    pub music: f32,
+}
+
+impl Default for Volumes {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            sfx: 0.75,
+            music: 0.5,
+        }
+    }
+}
+
+impl Volumes {
+    pub fn get(&self, channel: VolumeChannel) -> f32 {
+        match channel {
+            VolumeChannel::Master => self.master,
+            VolumeChannel::Sfx => self.sfx,
+            VolumeChannel::Music => self.music,
+        }
+    }
+
+    pub fn set(&mut self, channel: VolumeChannel, volume: f32) {
+        let volume = volume.clamp(0.0, 1.0);
+        match channel {
+            VolumeChannel::Master => self.master = volume,
+            VolumeChannel::Sfx => self.sfx = volume,
+            VolumeChannel::Music => self.music = volume,
+        }
+    }
+}
+
+enum Backend {
+    Mixer {
+        // the subsystem and the context close the audio when dropped, so they are kept while the game runs
+        _audio: AudioSubsystem,
+        _context: Sdl2MixerContext,
+        sounds: HashMap<Sound, Chunk>,
+        music: Option<Music<'static>>,
+    },
+    /// Used when there is no audio device, everything is accepted and nothing sounds.
+    Silent,
+}
+
+/// # Audio
+/// Plays the sound effects and the music of the game with SDL2_mixer.
+///
+/// If the audio can't be opened (for example a machine without an audio device) it falls back to a silent backend, so the game runs the same without sound.
+pub struct Audio {
+    backend: Backend,
+    volumes: Volumes,
+}
+
+impl Audio {
+    pub const MUSIC: &'static str = "assets/sounds/music.wav";
+    // sounds that can be heard at the same time
+    const CHANNELS: i32 = 16;
+
+    pub fn new(context: &Sdl) -> Self {
+        let backend = match Self::open(context) {
+            Ok(backend) => backend,
+            Err(err) => {
+                log::warn!("The audio wasn't initialized, the game runs without sound: {}", err);
+                Backend::Silent
+            },
+        };
+
+        Self { backend, volumes: Volumes::default() }
+    }
+
+    fn open(context: &Sdl) -> Result<Backend, String> {
+        let audio = context.audio()?;
+        mixer::open_audio(mixer::DEFAULT_FREQUENCY, mixer::DEFAULT_FORMAT, mixer::DEFAULT_CHANNELS, 1024)?;
+        let mixer_context = mixer::init(mixer::InitFlag::empty())?;
+        mixer::allocate_channels(Self::CHANNELS);
+
+        Ok(Backend::Mixer { _audio: audio, _context: mixer_context, sounds: HashMap::new(), music: None })
+    }
+
+    /// # Load Sounds
+    /// Loads every [`Sound`] from the assets, a sound that fails doesn't stop the others and is just not played.
+    ///
+    /// ## Returns:
+    /// - The errors of the sounds that couldn't be loaded, one per line.
+    pub fn load_sounds(&mut self, assets: &mut Assets) -> Result<(), String> {
+        let sounds = match &mut self.backend {
+            Backend::Mixer { sounds, .. } => sounds,
+            Backend::Silent => return Ok(()),
+        };
+        let mut errors = Vec::new();
+
+        for sound in Sound::ALL {
+            let loaded = assets.load_sound(sound.path()).and_then(|handle| {
+                let path = assets.sound(handle);
+                Chunk::from_file(path).map_err(|err| format!("The sound {} couldn't be loaded: {}", path.display(), err))
+            });
+
+            match loaded {
+                Ok(chunk) => {
+                    sounds.insert(sound, chunk);
+                },
+                Err(err) => errors.push(err),
+            }
+        }
+
+        self.apply_volumes();
+        if errors.is_empty() { Ok(()) } else { Err(errors.join("\n")) }
+    }
+
+    pub fn play(&self, sound: Sound) {
+        if let Backend::Mixer { sounds, .. } = &self.backend {
+            if let Some(chunk) = sounds.get(&sound) {
+                // when every channel is busy the sound is skipped, it's not worth stopping another one
+                let _ = Channel::all().play(chunk, 0);
+            }
+        }
+    }
+
+    /// Starts a music that loops until another one is played, the music that was playing is stopped.
+    pub fn play_music(&mut self, assets: &mut Assets, path: &str) -> Result<(), String> {
+        let music = match &mut self.backend {
+            Backend::Mixer { music, .. } => music,
+            Backend::Silent => return Ok(()),
+        };
+
+        let handle = assets.load_sound(path)?;
+        let path = assets.sound(handle);
+        let loaded = Music::from_file(path).map_err(|err| format!("The music {} couldn't be loaded: {}", path.display(), err))?;
+        loaded.play(-1)?;
+        *music = Some(loaded);
+
+        Ok(())
+    }
+
+    pub fn volumes(&self) -> Volumes {
+        self.volumes
+    }
+
+    pub fn set_volumes(&mut self, volumes: Volumes) {
+        self.volumes = volumes;
+        self.apply_volumes();
+    }
+
+    pub fn set_volume(&mut self, channel: VolumeChannel, volume: f32) {
+        self.volumes.set(channel, volume);
+        self.apply_volumes();
+    }
+
+    // the mixer volumes go from 0 to 128, the effects are changed on each chunk so the music isn't affected
+    fn apply_volumes(&mut self) {
+        let volumes = self.volumes;
+        if let Backend::Mixer { sounds, .. } = &mut self.backend {
+            let sfx = (volumes.master * volumes.sfx * mixer::MAX_VOLUME as f32).round() as i32;
+            for chunk in sounds.values_mut() {
+                chunk.set_volume(sfx);
+            }
+            Music::set_volume((volumes.master * volumes.music * mixer::MAX_VOLUME as f32).round() as i32);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use sdl2::rect::Rect;
+
+use crate::{game_object::GameObject, gameplay::server_game_logic::Position};
+
+/// # Camera
+/// Part of the world that is shown on the window, everything on the world is drawn through it so the world can be bigger than the window.
+///
+/// The `center` is the point of the world on the middle of the window, and the `zoom` is how many pixels of the window a unit of the world takes.
+pub struct Camera {
+    pub center: Position,
+    pub zoom: f32,
+    /// How fast the camera reaches its target, a higher value follows closer.
+    pub follow_speed: f32,
+    viewport: (u32, u32),
+}
+
+impl Camera {
+    pub const MIN_ZOOM: f32 = 0.5;
+    pub const MAX_ZOOM: f32 = 3.0;
+    const FOLLOW_SPEED: f32 = 8.0;
+
+    pub fn new(viewport: (u32, u32)) -> Self {
+        Self {
+            center: Position { x: viewport.0 as f32 / 2.0, y: viewport.1 as f32 / 2.0 },
+            zoom: 1.0,
+            follow_speed: Self::FOLLOW_SPEED,
+            viewport,
+        }
+    }
+
+    /// Size of the window in pixels, it's set every frame so a resize doesn't move the world.
+    pub fn set_viewport(&mut self, viewport: (u32, u32)) {
+        self.viewport = viewport;
+    }
+
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom.clamp(Self::MIN_ZOOM, Self::MAX_ZOOM);
+    }
+
+    /// # Follow
+    /// Moves the camera towards a point of the world, it covers a part of the distance each frame so it slows down when getting close.
+    ///
+    /// ## Params:
+    /// - target: Point of the world the camera wants to be centered on.
+    /// - delta_time: Seconds of the frame (from `Timing`), so the movement doesn't depend on the framerate.
+    pub fn follow(&mut self, target: Position, delta_time: f32) {
+        let amount = 1.0 - (-self.follow_speed * delta_time).exp();
+        self.center.x += (target.x - self.center.x) * amount;
+        self.center.y += (target.y - self.center.y) * amount;
+    }
+
+    /// Centers the camera on a point without the smooth movement, used when there is nothing to follow from.
+    pub fn snap_to(&mut self, target: Position) {
+        self.center = target;
+    }
+
+    /// # Clamp
+    /// Keeps the camera inside a world of the given size, so nothing outside the map is shown.
+    ///
+    /// If the world is smaller than the window on an axis it's centered on that axis.
+    pub fn clamp(&mut self, world_width: f32, world_height: f32) {
+        let half_width = self.viewport.0 as f32 / self.zoom / 2.0;
+        let half_height = self.viewport.1 as f32 / self.zoom / 2.0;
+
+        self.center.x = if world_width <= half_width * 2.0 { world_width / 2.0 } else { self.center.x.clamp(half_width, world_width - half_width) };
+        self.center.y = if world_height <= half_height * 2.0 { world_height / 2.0 } else { self.center.y.clamp(half_height, world_height - half_height) };
+    }
+
+    pub fn world_to_screen(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            (x - self.center.x) * self.zoom + self.viewport.0 as f32 / 2.0,
+            (y - self.center.y) * self.zoom + self.viewport.1 as f32 / 2.0,
+        )
+    }
+
+    pub fn screen_to_world(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            (x - self.viewport.0 as f32 / 2.0) / self.zoom + self.center.x,
+            (y - self.viewport.1 as f32 / 2.0) / self.zoom + self.center.y,
+        )
+    }
+
+    /// Rectangle of the window where a rectangle of the world is drawn.
+    pub fn rect(&self, game_object: &GameObject) -> Rect {
+        let (x, y) = self.world_to_screen(game_object.x, game_object.y);
+        Rect::new(x.round() as i32, y.round() as i32, (game_object.width * self.zoom).round().max(1.0) as u32, (game_object.height * self.zoom).round().max(1.0) as u32)
+    }
+}
@@ -0,0 +1,145 @@
+use sdl2::{render::Canvas, video::{FullscreenType, Window, WindowPos}, VideoSubsystem};
+use serde::{Deserialize, Serialize};
+
+use super::{log, settings::{ClientSettings, VideoSettings}};
+
+/// How the window is shown on the screen.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum WindowMode {
+    /// A normal window that can be resized and moved.
+    Windowed,
+    /// A window without borders that covers the screen, the display keeps its resolution.
+    Borderless,
+    /// The display is changed to the resolution of the settings.
+    Fullscreen,
+}
+
+impl WindowMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WindowMode::Windowed => "Windowed",
+            WindowMode::Borderless => "Borderless",
+            WindowMode::Fullscreen => "Fullscreen",
+        }
+    }
+
+    /// The mode after this one, the settings menu cycles through them.
+    pub fn next(&self) -> Self {
+        match self {
+            WindowMode::Windowed => WindowMode::Borderless,
+            WindowMode::Borderless => WindowMode::Fullscreen,
+            WindowMode::Fullscreen => WindowMode::Windowed,
+        }
+    }
+}
+
+/// Renderer name that lets SDL pick the best driver of the system.
+pub const AUTO_RENDERER: &str = "auto";
+
+/// # Renderers
+/// Names of the render drivers SDL was compiled with (like "opengl" or "software"), with [`AUTO_RENDERER`] first.
+pub fn renderers() -> Vec<String> {
+    let mut renderers = vec![String::from(AUTO_RENDERER)];
+    renderers.extend(sdl2::render::drivers().map(|info| info.name.to_string()));
+    renderers
+}
+
+/// # Resolutions
+/// Sizes the display of the window supports, from the smallest to the biggest.
+///
+/// ## Returns:
+/// - Every size at least as big as the minimum of the settings, with `current` added if the display doesn't list it (a window resized by hand).
+pub fn resolutions(video: &VideoSubsystem, display: i32, current: (u32, u32)) -> Vec<(u32, u32)> {
+    let mut resolutions = vec![current];
+
+    for index in 0..video.num_display_modes(display).unwrap_or(0) {
+        if let Ok(mode) = video.display_mode(display, index) {
+            let size = (mode.w as u32, mode.h as u32);
+            if size.0 >= ClientSettings::MIN_WIDTH && size.1 >= ClientSettings::MIN_HEIGHT {
+                resolutions.push(size);
+            }
+        }
+    }
+
+    // the same size is listed once per refresh rate and pixel format
+    resolutions.sort();
+    resolutions.dedup();
+    resolutions
+}
+
+/// # Create Canvas
+/// Opens the window and its renderer as the video settings say.
+///
+/// If the renderer of the settings doesn't work on this machine the window is opened again with the one SDL picks, so a bad setting never stops the game.
+pub fn create_canvas(video: &VideoSubsystem, title: &str, settings: &VideoSettings) -> Result<Canvas<Window>, String> {
+    let driver = if settings.renderer == AUTO_RENDERER {
+        None
+    } else {
+        match sdl2::render::drivers().position(|info| info.name == settings.renderer) {
+            Some(index) => Some(index as u32),
+            None => {
+                log::warn!("The renderer {} doesn't exist, the default one is used", settings.renderer);
+                None
+            },
+        }
+    };
+
+    match open(video, title, settings, driver) {
+        Err(err) if driver.is_some() => {
+            log::warn!("The renderer {} couldn't be used, the default one is used: {}", settings.renderer, err);
+            open(video, title, settings, None)
+        },
+        result => result,
+    }
+}
+
+fn open(video: &VideoSubsystem, title: &str, settings: &VideoSettings, driver: Option<u32>) -> Result<Canvas<Window>, String> {
+    let mut builder = video.window(title, settings.width, settings.height);
+    builder.position_centered().resizable();
+    match settings.mode {
+        WindowMode::Windowed => {},
+        WindowMode::Borderless => { builder.fullscreen_desktop(); },
+        WindowMode::Fullscreen => { builder.fullscreen(); },
+    }
+
+    let mut window = builder.build().map_err(|err| format!("The window wasn't created: {}", err))?;
+    window.set_minimum_size(ClientSettings::MIN_WIDTH, ClientSettings::MIN_HEIGHT).map_err(|err| err.to_string())?;
+
+    // without an index SDL tries the accelerated drivers first and the software one last
+    let mut canvas = window.into_canvas();
+    if let Some(index) = driver {
+        canvas = canvas.index(index);
+    }
+    if settings.vsync {
+        canvas = canvas.present_vsync();
+    }
+
+    canvas.build().map_err(|err| format!("The renderer wasn't created: {}", err))
+}
+
+/// # Apply Mode
+/// Changes the mode and the resolution of a window that is already open.
+///
+/// The vsync and the renderer belong to the canvas, and the textures of the game are made for it, so they are only changed when the game starts again.
+pub fn apply_mode(canvas: &mut Canvas<Window>, settings: &VideoSettings) -> Result<(), String> {
+    let window = canvas.window_mut();
+
+    match settings.mode {
+        WindowMode::Windowed => {
+            window.set_fullscreen(FullscreenType::Off)?;
+            window.set_size(settings.width, settings.height).map_err(|err| err.to_string())?;
+            window.set_position(WindowPos::Centered, WindowPos::Centered);
+        },
+        WindowMode::Borderless => window.set_fullscreen(FullscreenType::Desktop)?,
+        WindowMode::Fullscreen => {
+            // SDL uses the closest mode the display has, the refresh rate and format are kept
+            let mut mode = window.display_mode()?;
+            mode.w = settings.width as i32;
+            mode.h = settings.height as i32;
+            window.set_display_mode(mode)?;
+            window.set_fullscreen(FullscreenType::True)?;
+        },
+    }
+
+    Ok(())
+}
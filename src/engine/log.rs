@@ -0,0 +1,182 @@
+use std::{cmp::Reverse, collections::HashMap, fmt, fs::{self, File, OpenOptions}, io::Write, sync::{Mutex, OnceLock}, time::{SystemTime, UNIX_EPOCH}};
+
+use serde::{Deserialize, Serialize};
+
+use super::assets::asset_path;
+
+/// Importance of a log message, from the most important to the least.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    fn name(&self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+}
+
+/// # Log Settings
+/// Which messages are written and where, both the client and the server settings have them.
+///
+/// ## File example:
+/// ```json
+/// {
+///     "level": "Info",
+///     "modules": { "networking": "Debug", "networking::reliable": "Warn" },
+///     "file": "logs/server.log"
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct LogSettings {
+    /// Messages less important than this are not written.
+    pub level: Level,
+    /// Level of some modules (their path without the crate name), the longest path that matches a message is the one used.
+    pub modules: HashMap<String, Level>,
+    /// File the messages are also written to, they are added at the end of what it had.
+    pub file: Option<String>,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            level: Level::Info,
+            modules: HashMap::new(),
+            file: None,
+        }
+    }
+}
+
+struct Logger {
+    level: Level,
+    // sorted from the longest path, so the first one that matches is the most specific
+    modules: Vec<(String, Level)>,
+    file: Option<Mutex<File>>,
+}
+
+impl Logger {
+    fn new(settings: &LogSettings) -> Self {
+        let mut modules: Vec<(String, Level)> = settings.modules.iter().map(|(module, level)| (module.clone(), *level)).collect();
+        modules.sort_by_key(|(path, _)| Reverse(path.len()));
+
+        // the logger is not ready yet, so a file that can't be opened is reported directly
+        let file = settings.file.as_ref().and_then(|path| match Self::open(path) {
+            Ok(file) => Some(Mutex::new(file)),
+            Err(err) => {
+                eprintln!("{}, the log is only written on the terminal", err);
+                None
+            },
+        });
+
+        Self { level: settings.level, modules, file }
+    }
+
+    fn open(path: &str) -> Result<File, String> {
+        let path = asset_path(path);
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder).map_err(|err| format!("The folder {} couldn't be created: {}", folder.display(), err))?;
+        }
+        OpenOptions::new().create(true).append(true).open(&path).map_err(|err| format!("The log {} couldn't be opened: {}", path.display(), err))
+    }
+
+    fn enabled(&self, level: Level, module: &str) -> bool {
+        let limit = self.modules.iter()
+            .find(|(path, _)| module == path || module.starts_with(&format!("{}::", path)))
+            .map(|(_, level)| *level)
+            .unwrap_or(self.level);
+
+        level <= limit
+    }
+}
+
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+fn logger() -> &'static Logger {
+    LOGGER.get_or_init(|| Logger::new(&LogSettings::default()))
+}
+
+/// # Init
+/// Sets the settings of the log, it only works once so the server hosted from the menu keeps the log of the game.
+/// The messages written before it use the default settings.
+pub fn init(settings: &LogSettings) {
+    let _ = LOGGER.set(Logger::new(settings));
+}
+
+/// # Write
+/// Writes a message if its level is enabled for the module, the errors and warnings go to stderr and the rest to stdout.
+/// It's used through the [`error`], [`warn`], [`info`], [`debug`] and [`trace`] macros, they add the module.
+pub fn write(level: Level, module: &str, message: fmt::Arguments) {
+    // the crate name is the same on every module, so it's not part of the filters
+    let module = module.split_once("::").map(|(_, path)| path).unwrap_or(module);
+    let logger = logger();
+    if !logger.enabled(level, module) {
+        return;
+    }
+
+    let line = format!("{} {:<5} {}: {}", timestamp(), level.name(), module, message);
+    if level <= Level::Warn {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+
+    if let Some(file) = &logger.file {
+        if let Ok(mut file) = file.lock() {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+}
+
+// date and time in UTC like "2024-05-01 13:45:10.250", without a date library
+fn timestamp() -> String {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = now.as_secs();
+    let (days, time) = (seconds / 86_400, seconds % 86_400);
+
+    // civil date from the days since 1970, the algorithm of Howard Hinnant
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03}", year, month, day, time / 3_600, time % 3_600 / 60, time % 60, now.subsec_millis())
+}
+
+macro_rules! error {
+    ($($arg:tt)*) => { $crate::engine::log::write($crate::engine::log::Level::Error, module_path!(), format_args!($($arg)*)) };
+}
+
+// `warn` is also a built-in attribute, so the macro has another name and it's exported as `warn`
+macro_rules! warning {
+    ($($arg:tt)*) => { $crate::engine::log::write($crate::engine::log::Level::Warn, module_path!(), format_args!($($arg)*)) };
+}
+
+macro_rules! info {
+    ($($arg:tt)*) => { $crate::engine::log::write($crate::engine::log::Level::Info, module_path!(), format_args!($($arg)*)) };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => { $crate::engine::log::write($crate::engine::log::Level::Debug, module_path!(), format_args!($($arg)*)) };
+}
+
+macro_rules! trace {
+    ($($arg:tt)*) => { $crate::engine::log::write($crate::engine::log::Level::Trace, module_path!(), format_args!($($arg)*)) };
+}
+
+pub(crate) use {debug, error, info, trace, warning as warn};
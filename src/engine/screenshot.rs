@@ -0,0 +1,51 @@
+use std::{fs, path::{Path, PathBuf}, time::{SystemTime, UNIX_EPOCH}};
+
+use sdl2::{image::SaveSurface, pixels::PixelFormatEnum, render::Canvas, surface::Surface, video::Window};
+
+use super::assets::asset_path;
+
+/// Folder where the screenshots taken while playing are saved.
+pub const FOLDER: &str = "screenshots";
+
+/// # Capture
+/// Copies what was drawn on the canvas to a surface, it has to be called before `present` because after it the canvas content is undefined.
+///
+/// The pixels are always RGBA, so two captures of the same frame are equal byte by byte on any renderer.
+pub fn capture(canvas: &Canvas<Window>) -> Result<Surface<'static>, String> {
+    let (width, height) = canvas.output_size()?;
+    let format = PixelFormatEnum::RGBA32;
+    let pixels = canvas.read_pixels(None, format)?;
+    let mut surface = Surface::new(width, height, format)?;
+
+    // the rows of the surface can have padding at the end, so they are copied one by one
+    let row = width as usize * format.byte_size_per_pixel();
+    let pitch = surface.pitch() as usize;
+    surface.with_lock_mut(|destination| {
+        for (index, source) in pixels.chunks_exact(row).enumerate() {
+            destination[index * pitch..index * pitch + row].copy_from_slice(source);
+        }
+    });
+
+    Ok(surface)
+}
+
+/// Saves a surface as a png, the folder is created if it's missing.
+pub fn save_png(surface: &Surface, path: &Path) -> Result<(), String> {
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder).map_err(|err| format!("The folder {} couldn't be created: {}", folder.display(), err))?;
+    }
+    surface.save(path).map_err(|err| format!("The screenshot {} couldn't be saved: {}", path.display(), err))
+}
+
+/// # Take
+/// Captures the canvas and saves it on [`FOLDER`] with the time as the name, so a screenshot never replaces another.
+///
+/// ## Returns:
+/// - The path of the png that was written.
+pub fn take(canvas: &Canvas<Window>) -> Result<PathBuf, String> {
+    let millis = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_millis()).unwrap_or(0);
+    let path = asset_path(FOLDER).join(format!("arrownier-{}.png", millis));
+
+    save_png(&capture(canvas)?, &path)?;
+    Ok(path)
+}
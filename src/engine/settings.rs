@@ -0,0 +1,200 @@
+use std::{fs, net::SocketAddr, path::PathBuf};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{engine::{assets::asset_path, audio::Volumes, display::{self, WindowMode}, log::LogSettings}, gameplay::{combat::CombatConfig, map::MapData, player_name::{self, MAX_NAME_LENGTH}}, input::bindings::Bindings};
+
+/// # Load Or Create
+/// Reads a settings file, the first time the game runs the file doesn't exist so it's written with the default values.
+///
+/// ## Returns:
+/// - The settings of the file (or the defaults if it was just created), or an error that says which file failed and why.
+pub fn load_or_create<T: Serialize + DeserializeOwned + Default>(path: &str) -> Result<T, String> {
+    let resolved = asset_path(path);
+    if !resolved.exists() {
+        let settings = T::default();
+        save(path, &settings)?;
+        return Ok(settings);
+    }
+
+    let content = fs::read_to_string(&resolved).map_err(|err| format!("The settings {} couldn't be read: {}", resolved.display(), err))?;
+    serde_json::from_str(&content).map_err(|err| format!("The settings {} are not valid: {}", resolved.display(), err))
+}
+
+/// Writes a settings file as pretty json, the folder is created if it's missing.
+pub fn save<T: Serialize>(path: &str, settings: &T) -> Result<(), String> {
+    let path = asset_path(path);
+    let content = serde_json::to_string_pretty(settings).map_err(|err| format!("The settings {} couldn't be saved: {}", path.display(), err))?;
+
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder).map_err(|err| format!("The folder {} couldn't be created: {}", folder.display(), err))?;
+    }
+    fs::write(&path, content).map_err(|err| format!("The settings {} couldn't be saved: {}", path.display(), err))
+}
+
+/// # Back Up
+/// Copies a settings file that couldn't be loaded next to it with a `.bad` extension, so saving the defaults over it doesn't lose what the player wrote.
+///
+/// ## Returns:
+/// - The path of the copy, or `None` if there was no file to copy.
+pub fn back_up(path: &str) -> Result<Option<PathBuf>, String> {
+    let resolved = asset_path(path);
+    if !resolved.exists() {
+        return Ok(None);
+    }
+
+    let mut backup = resolved.clone().into_os_string();
+    backup.push(".bad");
+    let backup = PathBuf::from(backup);
+    fs::copy(&resolved, &backup).map_err(|err| format!("The settings {} couldn't be copied to {}: {}", resolved.display(), backup.display(), err))?;
+    Ok(Some(backup))
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct VideoSettings {
+    /// Size of the window, on fullscreen it's the resolution of the display. A window resized by hand saves its new size.
+    pub width: u32,
+    pub height: u32,
+    pub mode: WindowMode,
+    /// Waits for the display to show each frame, the change is applied when the game starts again.
+    pub vsync: bool,
+    /// Frames each second at most, 0 doesn't limit them.
+    pub frame_cap: u32,
+    /// Render driver of SDL (like "opengl", "direct3d" or "software"), "auto" lets SDL pick it. It's applied when the game starts again.
+    pub renderer: String,
+    pub show_framerate: bool,
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self {
+            width: 1280,
+            height: 720,
+            mode: WindowMode::Windowed,
+            vsync: true,
+            frame_cap: 0,
+            renderer: String::from(display::AUTO_RENDERER),
+            show_framerate: true,
+        }
+    }
+}
+
+/// # Client Settings
+/// Everything the player can change on the game, it's loaded when the game starts and saved each time something changes.
+///
+/// The app keeps the values on its own fields, this is only the shape of the file.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ClientSettings {
+    pub video: VideoSettings,
+    pub audio: Volumes,
+    pub controls: Bindings,
+    /// Address of the last server joined, it's written on the connect screen.
+    pub last_server: String,
+    pub name: String,
+    pub log: LogSettings,
+}
+
+impl ClientSettings {
+    pub const DEFAULT_PATH: &'static str = "config/client.json";
+    pub const MIN_WIDTH: u32 = 640;
+    pub const MIN_HEIGHT: u32 = 360;
+    pub const MAX_FRAME_CAP: u32 = 1000;
+
+    /// # Load
+    /// Reads the settings and checks them, so a value written by hand can't break the game.
+    pub fn load(path: &str) -> Result<Self, String> {
+        let settings: Self = load_or_create(path)?;
+        settings.validate().map_err(|err| format!("The settings {} are not valid: {}", asset_path(path).display(), err))?;
+        Ok(settings)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        save(path, self)
+    }
+
+    /// The name the player used last time, or the default one if the file doesn't have it.
+    pub fn player_name(&self) -> String {
+        player_name::sanitize_name(&self.name)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.video.width < Self::MIN_WIDTH || self.video.height < Self::MIN_HEIGHT {
+            return Err(format!("the window must be at least {}x{}", Self::MIN_WIDTH, Self::MIN_HEIGHT));
+        }
+        if self.video.frame_cap > Self::MAX_FRAME_CAP {
+            return Err(format!("the frame cap can't be more than {}", Self::MAX_FRAME_CAP));
+        }
+
+        for (name, volume) in [("master", self.audio.master), ("sfx", self.audio.sfx), ("music", self.audio.music)] {
+            if !(0.0..=1.0).contains(&volume) {
+                return Err(format!("the {} volume must be between 0 and 1", name));
+            }
+        }
+
+        if self.name.chars().count() > MAX_NAME_LENGTH {
+            return Err(format!("the name can't be longer than {} characters", MAX_NAME_LENGTH));
+        }
+
+        Ok(())
+    }
+}
+
+/// # Server Settings
+/// Settings of the server, both the dedicated one and the one hosted from the menu use them.
+///
+/// The movement and the team rules have their own files on `assets/config`, they are tunables of the game more than of the server.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ServerSettings {
+    /// Address the socket is bound to, a port of 0 lets the system pick a free one.
+    pub bind_address: String,
+    /// Ticks of the game logic each second.
+    pub tick_rate: f32,
+    /// Players that can be connected at the same time, the next ones are rejected.
+    pub max_players: usize,
+    /// Path of the map that is played.
+    pub map: String,
+    pub combat: CombatConfig,
+    pub log: LogSettings,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            bind_address: String::from("0.0.0.0:0"),
+            tick_rate: 60.0,
+            max_players: 16,
+            map: String::from(MapData::DEFAULT_PATH),
+            combat: CombatConfig::default(),
+            log: LogSettings::default(),
+        }
+    }
+}
+
+impl ServerSettings {
+    pub const DEFAULT_PATH: &'static str = "config/server.json";
+    pub const MAX_TICK_RATE: f32 = 240.0;
+
+    /// # Load
+    /// Reads the settings and checks them, the server doesn't start with a value it can't use.
+    pub fn load(path: &str) -> Result<Self, String> {
+        let settings: Self = load_or_create(path)?;
+        settings.validate().map_err(|err| format!("The settings {} are not valid: {}", asset_path(path).display(), err))?;
+        Ok(settings)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        self.bind_address.parse::<SocketAddr>().map_err(|err| format!("the bind address {} is not an ip:port ({})", self.bind_address, err))?;
+
+        if !(self.tick_rate >= 1.0 && self.tick_rate <= Self::MAX_TICK_RATE) {
+            return Err(format!("the tick rate must be between 1 and {}", Self::MAX_TICK_RATE));
+        }
+        if self.max_players == 0 {
+            return Err(String::from("the server needs at least one player"));
+        }
+
+        self.combat.validate()
+    }
+}
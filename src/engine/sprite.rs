@@ -0,0 +1,173 @@
+use std::{collections::HashMap, fs};
+
+use sdl2::{pixels::Color, rect::Rect, render::{Canvas, TextureQuery}, video::Window};
+use serde::{Deserialize, Serialize};
+
+use super::assets::{asset_path, Assets, TextureHandle};
+
+fn default_frame_time() -> f32 {
+    0.1
+}
+
+fn default_looping() -> bool {
+    true
+}
+
+/// A group of frames that go one after another on the sheet.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct Animation {
+    pub first_frame: u32,
+    pub frames: u32,
+    /// Seconds each frame is shown.
+    #[serde(default = "default_frame_time")]
+    pub frame_time: f32,
+    /// When disabled the animation stays on its last frame.
+    #[serde(default = "default_looping")]
+    pub looping: bool,
+}
+
+/// Description of a sprite sheet on the sprites config.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SheetConfig {
+    pub texture: String,
+    pub frame_width: u32,
+    pub frame_height: u32,
+    #[serde(default)]
+    pub animations: HashMap<String, Animation>,
+}
+
+/// # Sprite Config
+/// Sprite sheets of the game by name, they are loaded from a json file so they can be changed without compiling again.
+///
+/// ## File example:
+/// ```json
+/// {
+///     "player": {
+///         "texture": "assets/sprites/player.png",
+///         "frame_width": 16,
+///         "frame_height": 16,
+///         "animations": { "walk": { "first_frame": 0, "frames": 4, "frame_time": 0.12 } }
+///     }
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct SpriteConfig {
+    #[serde(flatten)]
+    pub sheets: HashMap<String, SheetConfig>,
+}
+
+impl SpriteConfig {
+    pub const DEFAULT_PATH: &'static str = "assets/config/sprites.json";
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let path = asset_path(path);
+        let content = fs::read_to_string(&path).map_err(|err| format!("The sprite config {} couldn't be read: {}", path.display(), err))?;
+        let config: SpriteConfig = serde_json::from_str(&content).map_err(|err| format!("The sprite config {} is not valid: {}", path.display(), err))?;
+
+        for (name, sheet) in &config.sheets {
+            if sheet.frame_width == 0 || sheet.frame_height == 0 {
+                return Err(format!("The sprite sheet {} of {} must have a positive frame size", name, path.display()));
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+/// # Sprite Sheet
+/// A texture split in frames of the same size (a texture atlas), the frames are counted from left to right and from top to bottom.
+pub struct SpriteSheet {
+    pub texture: TextureHandle,
+    pub frame_width: u32,
+    pub frame_height: u32,
+    columns: u32,
+    animations: HashMap<String, Animation>,
+}
+
+impl SpriteSheet {
+    pub fn load(assets: &mut Assets, config: &SheetConfig) -> Result<Self, String> {
+        let texture = assets.load_texture(&config.texture)?;
+        let TextureQuery { width, .. } = assets.texture(texture).query();
+
+        Ok(Self {
+            texture,
+            frame_width: config.frame_width,
+            frame_height: config.frame_height,
+            columns: (width / config.frame_width).max(1),
+            animations: config.animations.clone(),
+        })
+    }
+
+    pub fn animation(&self, name: &str) -> Option<&Animation> {
+        self.animations.get(name)
+    }
+
+    pub fn frame_rect(&self, frame: u32) -> Rect {
+        let column = frame % self.columns;
+        let row = frame / self.columns;
+        Rect::new((column * self.frame_width) as i32, (row * self.frame_height) as i32, self.frame_width, self.frame_height)
+    }
+
+    /// # Draw
+    /// Copies a frame of the sheet on the canvas, the [`DrawParams`] say which frame and how it's drawn.
+    pub fn draw(&self, canvas: &mut Canvas<Window>, assets: &Assets, destination: Rect, params: DrawParams) {
+        let mut texture = assets.texture(self.texture);
+        texture.set_color_mod(params.tint.r, params.tint.g, params.tint.b);
+        let _ = canvas.copy_ex(&texture, self.frame_rect(params.frame), destination, params.angle, None, params.flip, false);
+        texture.set_color_mod(255, 255, 255);
+    }
+}
+
+/// # Draw Params
+/// How a frame of a [`SpriteSheet`] is drawn, the default is the first frame as it is on the sheet.
+#[derive(Clone, Copy)]
+pub struct DrawParams {
+    pub frame: u32,
+    /// Degrees the frame is rotated (clockwise) around its center.
+    pub angle: f64,
+    /// Mirrors the frame horizontally, used to look to the left.
+    pub flip: bool,
+    /// Color the frame is multiplied by, white keeps the original colors.
+    pub tint: Color,
+}
+
+impl Default for DrawParams {
+    fn default() -> Self {
+        Self { frame: 0, angle: 0.0, flip: false, tint: Color::WHITE }
+    }
+}
+
+/// # Animator
+/// Keeps the time of the animation of one entity, each entity has its own so they don't move in sync.
+#[derive(Default)]
+pub struct Animator {
+    current: String,
+    elapsed: f32,
+}
+
+impl Animator {
+    /// Changes the animation being played, it starts from the beginning only if it's a different one.
+    pub fn play(&mut self, name: &str) {
+        if self.current != name {
+            self.current = name.to_string();
+            self.elapsed = 0.0;
+        }
+    }
+
+    /// Advances the animation by the `delta_time` of the frame (from `Timing`).
+    pub fn update(&mut self, delta_time: f32) {
+        self.elapsed += delta_time;
+    }
+
+    /// The frame of the sheet that goes now, the first one if the sheet doesn't have the animation.
+    pub fn frame(&self, sheet: &SpriteSheet) -> u32 {
+        let animation = match sheet.animation(&self.current) {
+            Some(animation) if animation.frames > 0 => animation,
+            _ => return 0,
+        };
+
+        let step = (self.elapsed / animation.frame_time.max(0.001)) as u32;
+        let step = if animation.looping { step % animation.frames } else { step.min(animation.frames - 1) };
+        animation.first_frame + step
+    }
+}
@@ -1,4 +1,4 @@
-use std::time::{Duration, Instant};
+use std::{thread, time::{Duration, Instant}};
 
 pub struct Timing {
     pub delta_time_duration: Duration,
@@ -46,6 +46,22 @@ impl Timing {
         self.fps
     }
 
+    /// # Limit
+    /// Sleeps what is left of the frame so there are at most `frame_cap` frames each second, it's called after the frame is shown.
+    ///
+    /// A `frame_cap` of 0 doesn't wait.
+    pub fn limit(&self, frame_cap: u32) {
+        if frame_cap == 0 {
+            return;
+        }
+
+        let frame = Duration::from_secs_f64(1.0 / frame_cap as f64);
+        let elapsed = self.last_frame.elapsed();
+        if elapsed < frame {
+            thread::sleep(frame - elapsed);
+        }
+    }
+
     pub fn get_fps(&mut self) -> f32 {
         self.fps
     }
@@ -9,4 +9,33 @@ pub struct GameObject {
     pub y: f32,
     pub width: f32,
     pub height: f32,
-}
\ No newline at end of file
+}
+
+impl GameObject {
+    /// # Overlap
+    /// Checks if two objects are overlapping as axis aligned boxes.
+    ///
+    /// ## Returns:
+    /// - The smallest (x, y) displacement that has to be applied to `self` to stop overlapping `other`, only one of the axis will be different from 0, or `None` if they are not touching.
+    pub fn overlap(&self, other: &GameObject) -> Option<(f32, f32)> {
+        let overlap_x = (self.x + self.width).min(other.x + other.width) - self.x.max(other.x);
+        let overlap_y = (self.y + self.height).min(other.y + other.height) - self.y.max(other.y);
+
+        if overlap_x <= 0.0 || overlap_y <= 0.0 {
+            return None;
+        }
+
+        // we push on the axis that needs less movement, and away from the center of the other object
+        if overlap_x < overlap_y {
+            let direction = if self.x + self.width / 2.0 < other.x + other.width / 2.0 { -1.0 } else { 1.0 };
+            Some((overlap_x * direction, 0.0))
+        } else {
+            let direction = if self.y + self.height / 2.0 < other.y + other.height / 2.0 { -1.0 } else { 1.0 };
+            Some((0.0, overlap_y * direction))
+        }
+    }
+
+    pub fn contains_point(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
+    }
+}
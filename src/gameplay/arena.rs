@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game_object::GameObject;
+
+/// # Arena
+/// The playable area of the server, players can't leave it and arrows are removed once they go outside of it.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct Arena {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for Arena {
+    // by default the arena is the size of the client window
+    fn default() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            width: 1280.0,
+            height: 720.0,
+        }
+    }
+}
+
+impl Arena {
+    /// Moves the object back inside of the arena if any part of it is outside.
+    pub fn clamp(&self, object: &mut GameObject) {
+        object.x = object.x.clamp(self.x, (self.x + self.width - object.width).max(self.x));
+        object.y = object.y.clamp(self.y, (self.y + self.height - object.height).max(self.y));
+    }
+
+    pub fn contains_point(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
+    }
+}
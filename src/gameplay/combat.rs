@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// # Combat Config
+/// Tunables of the arrows, they are part of the server settings.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct CombatConfig {
+    /// Pixels per second an arrow flies.
+    pub arrow_speed: f32,
+    /// Health a player loses when an arrow hits it.
+    pub arrow_damage: f32,
+    /// Seconds an arrow flies before disappearing.
+    pub arrow_lifetime: f32,
+    /// Seconds a player waits between two arrows.
+    pub fire_cooldown: f32,
+}
+
+impl Default for CombatConfig {
+    fn default() -> Self {
+        Self {
+            arrow_speed: 600.0,
+            arrow_damage: 25.0,
+            arrow_lifetime: 2.0,
+            fire_cooldown: 0.5,
+        }
+    }
+}
+
+impl CombatConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.arrow_speed <= 0.0 || self.arrow_lifetime <= 0.0 {
+            return Err(String::from("the arrows need a positive speed and lifetime"));
+        }
+        if self.arrow_damage < 0.0 || self.fire_cooldown < 0.0 {
+            return Err(String::from("the arrow damage and the fire cooldown can't be negative"));
+        }
+
+        Ok(())
+    }
+}
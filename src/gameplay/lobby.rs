@@ -0,0 +1,364 @@
+use std::{net::ToSocketAddrs, time::{Duration, Instant}};
+
+use sdl2::{event::Event, keyboard::Keycode, pixels::Color};
+
+use crate::{app::{App, AppState, GameState}, engine::{assets::Assets, log}, gameplay::{menu::{label_place, menu_button, menu_label, menu_stack}, player_name::MAX_NAME_LENGTH}, input::text_input::TextInput, networking::protocol::ClientMessage, ui::{layout::{Anchor, Layout}, manager::UiManager, text_cache::TextCache}};
+
+// characters used by ip addresses, host names and ports
+fn address_character(character: char) -> bool {
+    character.is_ascii_alphanumeric() || character == '.' || character == ':' || character == '-' || character == '[' || character == ']'
+}
+
+fn validate_address(address: &str) -> Result<(), String> {
+    match address.trim().rsplit_once(':') {
+        Some((host, port)) if !host.is_empty() => port.parse::<u16>().map(|_| ()).map_err(|_| String::from("The port must be a number between 0 and 65535")),
+        _ => Err(String::from("Write the address as ip:port")),
+    }
+}
+
+// same characters that the server keeps on a name
+fn name_character(character: char) -> bool {
+    character.is_alphanumeric() || character == ' ' || character == '_' || character == '-'
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        Err(String::from("Write your name"))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ConnectAction {
+    Connect,
+    Cancel,
+}
+
+/// # Connect Screen
+/// Shown while we wait for the server to welcome us, when joining it also asks for the address of the server and the name of the player.
+pub struct ConnectScreen {
+    ui: UiManager<ConnectAction>,
+    status: usize,
+    // widgets only shown while we are asking for the address
+    join_widgets: Vec<usize>,
+    address: TextInput,
+    name: TextInput,
+    // problem found with the address or the name, shown instead of the status
+    error: Option<String>,
+    last_join_sent: Option<Instant>,
+}
+
+impl ConnectScreen {
+    // time waited for the welcome of the server before asking for it again
+    const JOIN_RETRY: Duration = Duration::from_millis(500);
+
+    pub fn new(app: &mut App) -> Self {
+        // the last server joined is written again, so joining the same one is only pressing connect
+        let last_server = if app.last_server.is_empty() { "127.0.0.1:" } else { app.last_server.as_str() };
+        let mut address = TextInput::new(Self::address_place().resolve((1280, 720)), last_server, "ip:port", 64);
+        address.allowed = Some(address_character);
+        address.validate = Some(validate_address);
+        address.focus();
+
+        let mut name = TextInput::new(Self::name_place().resolve((1280, 720)), &app.preferred_name, "Your name", MAX_NAME_LENGTH);
+        name.allowed = Some(name_character);
+        name.validate = Some(validate_name);
+
+        let buttons = menu_stack(112.0);
+
+        let mut ui = UiManager::new();
+        let status = ui.add_label(label_place(-105.0), menu_label("Connecting"));
+        let join_widgets = vec![
+            ui.add_label(label_place(-65.0), menu_label("Server address")),
+            ui.add_label(label_place(15.0), menu_label("Your name")),
+            ui.add_button(buttons.item(0), menu_button("Connect"), ConnectAction::Connect),
+        ];
+        ui.add_button(buttons.item(1), menu_button("Cancel"), ConnectAction::Cancel);
+
+        Self {
+            ui,
+            status,
+            join_widgets,
+            address,
+            name,
+            error: None,
+            last_join_sent: None,
+        }
+    }
+
+    fn address_place() -> Layout {
+        Layout::pixels(Anchor::Center, 240.0, 36.0).offset(0.0, -32.0)
+    }
+
+    fn name_place() -> Layout {
+        Layout::pixels(Anchor::Center, 240.0, 36.0).offset(0.0, 48.0)
+    }
+
+    pub fn update(&mut self, assets: &Assets, text_cache: &mut TextCache, app_state: &mut AppState, event_pump: &mut sdl2::EventPump, app: &mut App) {
+        let font = assets.text_font();
+        let mut cancel = false;
+        let mut connect = false;
+
+        // the inputs are not owned by the ui manager, so they are moved with it
+        let window = app.canvas.output_size().unwrap_or((1280, 720));
+        if self.ui.layout(window) {
+            self.address.game_object = Self::address_place().resolve(window);
+            self.name.game_object = Self::name_place().resolve(window);
+        }
+
+        // the inputs are only used while we don't have a connection (when hosting we are already connected)
+        let asking = app.connection.is_none();
+        self.address.game_object.active = asking;
+        self.name.game_object.active = asking;
+        for id in &self.join_widgets {
+            self.ui.get_mut(*id).game_object.active = asking;
+        }
+
+        for event in event_pump.poll_iter() {
+            app.gamepads.handle_event(&event);
+            if asking && (self.address.handle_event(&event, &app.clipboard) | self.name.handle_event(&event, &app.clipboard)) {
+                continue;
+            }
+
+            // while writing the keyboard is used by the inputs instead of moving between the buttons
+            let typing = self.address.focused || self.name.focused;
+
+            match event {
+                Event::Quit { .. } => app_state.is_running = false,
+                Event::KeyDown { keycode: Some(Keycode::Escape), .. } => cancel = true,
+                Event::KeyDown { keycode: Some(Keycode::Return), .. } if typing => connect = true,
+                Event::KeyDown { keycode: Some(Keycode::Tab), .. } if typing => {
+                    // tab moves between the two inputs
+                    if self.address.focused {
+                        self.address.unfocus();
+                        self.name.focus();
+                    } else {
+                        self.name.unfocus();
+                        self.address.focus();
+                    }
+                },
+                _ => {}
+            }
+
+            if typing && matches!(event, Event::KeyDown { .. }) {
+                continue;
+            }
+
+            match self.ui.handle_event(&event) {
+                Some(ConnectAction::Connect) => connect = true,
+                Some(ConnectAction::Cancel) => cancel = true,
+                None => {}
+            }
+        }
+
+        if cancel {
+            app.disconnect();
+            self.error = None;
+            self.last_join_sent = None;
+            self.ui.clear_focus();
+            self.address.focus();
+            app_state.state = GameState::Menu;
+            return;
+        }
+
+        match &app.connection {
+            None => {
+                if connect {
+                    self.connect_to_server(app_state, app);
+                }
+
+                let status = self.ui.get_mut(self.status);
+                match &self.error {
+                    Some(err) => {
+                        status.text = Some(err.clone());
+                        status.text_color = Color::RGB(230, 90, 90);
+                    },
+                    None => {
+                        status.text = Some(String::from("Join a server"));
+                        status.text_color = Color::WHITE;
+                    },
+                }
+            },
+            Some(connection) => {
+                if app.map.is_some() {
+                    self.last_join_sent = None;
+                    self.ui.clear_focus();
+                    app_state.state = GameState::Lobby;
+                    return;
+                }
+
+                let status = self.ui.get_mut(self.status);
+                status.text = Some(format!("Connecting to {}...", connection.connect_to));
+                status.text_color = Color::WHITE;
+
+                // until the server sends us the map we keep asking to join
+                let retry = match self.last_join_sent {
+                    Some(last_join) => last_join.elapsed() >= Self::JOIN_RETRY,
+                    None => true,
+                };
+
+                if retry {
+                    let join = ClientMessage::Join { name: app.player_name.clone() };
+                    app.send(&join);
+                    self.last_join_sent = Some(Instant::now());
+                }
+            },
+        }
+
+        self.ui.render(&mut app.canvas, text_cache, font);
+        self.address.render(&mut app.canvas, text_cache, font);
+        self.name.render(&mut app.canvas, text_cache, font);
+    }
+
+    fn connect_to_server(&mut self, app_state: &mut AppState, app: &mut App) {
+        // the first problem found is shown on the status line
+        if let Err(err) = self.address.validation().and(self.name.validation()) {
+            self.error = Some(err);
+            return;
+        }
+
+        let address = self.address.text.trim().to_string();
+        if let Err(err) = address.to_socket_addrs() {
+            self.error = Some(format!("The address {} is not valid: {}", address, err));
+            return;
+        }
+
+        self.error = None;
+        let name = self.name.text.trim().to_string();
+        if let Err(err) = app.connect(&address, &name) {
+            app_state.disconnected(&err);
+            return;
+        }
+
+        app.last_server = address;
+        app.preferred_name = name;
+        if let Err(err) = app.save_settings() {
+            log::error!("{}", err);
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum LobbyAction {
+    Play,
+    Leave,
+}
+
+/// # Lobby
+/// Shown once the server welcomed us, it has the data of the map and the players that are already playing.
+pub struct Lobby {
+    ui: UiManager<LobbyAction>,
+    title: usize,
+    info: usize,
+    players: usize,
+}
+
+impl Lobby {
+    pub fn new() -> Self {
+        let buttons = menu_stack(42.0);
+
+        let mut ui = UiManager::new();
+        let title = ui.add_label(label_place(-105.0), menu_label("Lobby"));
+        let info = ui.add_label(label_place(-65.0), menu_label(""));
+        let players = ui.add_label(label_place(-25.0), menu_label(""));
+        ui.add_button(buttons.item(0), menu_button("Play"), LobbyAction::Play);
+        ui.add_button(buttons.item(1), menu_button("Leave"), LobbyAction::Leave);
+
+        Self { ui, title, info, players }
+    }
+
+    pub fn update(&mut self, assets: &Assets, text_cache: &mut TextCache, app_state: &mut AppState, event_pump: &mut sdl2::EventPump, app: &mut App) {
+        let font = assets.text_font();
+        let mut clicked = None;
+        self.ui.layout(app.canvas.output_size().unwrap_or((1280, 720)));
+
+        for event in event_pump.poll_iter() {
+            app.gamepads.handle_event(&event);
+            match event {
+                Event::Quit { .. } => app_state.is_running = false,
+                Event::KeyDown { keycode: Some(Keycode::Escape), .. } => clicked = Some(LobbyAction::Leave),
+                _ => {}
+            }
+
+            if let Some(action) = self.ui.handle_event(&event) {
+                clicked = Some(action);
+            }
+        }
+
+        match clicked {
+            Some(LobbyAction::Play) => {
+                self.ui.clear_focus();
+                app.send(&ClientMessage::Ready);
+                app_state.state = GameState::Playing;
+            },
+            Some(LobbyAction::Leave) => {
+                self.ui.clear_focus();
+                app.disconnect();
+                app_state.state = GameState::Menu;
+                return;
+            },
+            None => {}
+        }
+
+        if let Some(map) = &app.map {
+            self.ui.get_mut(self.title).text = Some(format!("Lobby - {}", map.name));
+            self.ui.get_mut(self.info).text = Some(if map.author.is_empty() { format!("Playing as {}", app.player_name) } else { format!("Map by {} - Playing as {}", map.author, app.player_name) });
+        }
+
+        let names: Vec<String> = match &app.received {
+            Some(returned) => returned.players_data.values().map(|player| player.name.clone()).collect(),
+            None => Vec::new(),
+        };
+        self.ui.get_mut(self.players).text = Some(if names.is_empty() { String::from("Nobody is playing yet") } else { format!("In game: {}", names.join(", ")) });
+
+        self.ui.render(&mut app.canvas, text_cache, font);
+    }
+}
+
+/// # Disconnected Screen
+/// Shown when the connection fails or is lost, it tells the reason to the player.
+pub struct DisconnectedScreen {
+    // the only button goes back to the menu, so it doesn't need an action
+    ui: UiManager<()>,
+    reason: usize,
+}
+
+impl DisconnectedScreen {
+    pub fn new() -> Self {
+        let mut ui = UiManager::new();
+        ui.add_label(label_place(-65.0), menu_label("Disconnected"));
+        // the reason can be a long error, so it's split in lines
+        let mut reason_label = menu_label("");
+        reason_label.wrap = true;
+        let reason = ui.add_label(label_place(-25.0), reason_label);
+        ui.add_button(menu_stack(42.0).item(0), menu_button("Back to menu"), ());
+
+        Self { ui, reason }
+    }
+
+    pub fn update(&mut self, assets: &Assets, text_cache: &mut TextCache, app_state: &mut AppState, event_pump: &mut sdl2::EventPump, app: &mut App) {
+        let font = assets.text_font();
+        self.ui.layout(app.canvas.output_size().unwrap_or((1280, 720)));
+
+        for event in event_pump.poll_iter() {
+            app.gamepads.handle_event(&event);
+            match event {
+                Event::Quit { .. } => app_state.is_running = false,
+                Event::KeyDown { keycode: Some(Keycode::Escape), .. } | Event::KeyDown { keycode: Some(Keycode::Return), .. } => app_state.state = GameState::Menu,
+                _ => {}
+            }
+
+            if self.ui.handle_event(&event).is_some() {
+                app_state.state = GameState::Menu;
+            }
+        }
+
+        if app_state.state != GameState::Disconnected {
+            self.ui.clear_focus();
+        }
+
+        self.ui.get_mut(self.reason).text = Some(app_state.disconnect_reason.clone());
+        self.ui.render(&mut app.canvas, text_cache, font);
+    }
+}
@@ -0,0 +1,100 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{engine::assets::asset_path, game_object::GameObject};
+
+use super::{arena::Arena, pickup::PickupSpawn, server_game_logic::Position};
+
+/// A solid rectangle of the map, players can't walk through it and arrows are destroyed when hitting it.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct Wall {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Wall {
+    pub fn game_object(&self) -> GameObject {
+        GameObject {
+            active: true,
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+/// # Map Data
+/// Description of a map loaded from a json file, the server loads it and sends it to every client when they join.
+///
+/// ## File example:
+/// ```json
+/// {
+///     "name": "Arena",
+///     "author": "Khalzz",
+///     "width": 1280.0,
+///     "height": 720.0,
+///     "walls": [{ "x": 300.0, "y": 160.0, "width": 40.0, "height": 160.0 }],
+///     "spawn_points": [{ "x": 40.0, "y": 40.0 }],
+///     "pickups": [{ "kind": "Health", "position": { "x": 620.0, "y": 60.0 } }, { "kind": "Points", "respawn_time": 5.0 }]
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MapData {
+    pub name: String,
+    #[serde(default)]
+    pub author: String,
+    pub width: f32,
+    pub height: f32,
+    #[serde(default)]
+    pub walls: Vec<Wall>,
+    #[serde(default)]
+    pub spawn_points: Vec<Position>,
+    #[serde(default)]
+    pub pickups: Vec<PickupSpawn>,
+}
+
+impl Default for MapData {
+    // an empty map with the size of the default arena
+    fn default() -> Self {
+        let arena = Arena::default();
+
+        Self {
+            name: String::from("Empty"),
+            author: String::new(),
+            width: arena.width,
+            height: arena.height,
+            walls: Vec::new(),
+            spawn_points: Vec::new(),
+            pickups: Vec::new(),
+        }
+    }
+}
+
+impl MapData {
+    pub const DEFAULT_PATH: &'static str = "assets/maps/arena.json";
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let path = asset_path(path);
+        let content = fs::read_to_string(&path).map_err(|err| format!("The map {} couldn't be read: {}", path.display(), err))?;
+        let map: MapData = serde_json::from_str(&content).map_err(|err| format!("The map {} is not valid: {}", path.display(), err))?;
+
+        if map.width <= 0.0 || map.height <= 0.0 {
+            return Err(format!("The map {} must have a positive width and height", path.display()));
+        }
+
+        Ok(map)
+    }
+
+    pub fn arena(&self) -> Arena {
+        Arena {
+            x: 0.0,
+            y: 0.0,
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// # Match Phase
+/// The phases a match goes through, the server is the only one that changes them, the client just reads them from the [`MatchState`] it receives.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+pub enum MatchPhase {
+    /// Players can move and shoot but points are not counted, the countdown only runs once there are enough players.
+    Warmup,
+    /// The round itself, points are counted until the countdown reaches zero.
+    InProgress,
+    /// Short pause after the countdown where nobody can shoot.
+    RoundEnd,
+    /// The final results are shown to every player before going back to warmup.
+    Scoreboard,
+}
+
+/// # Match State
+/// Server-authoritative state of the current match, it is replicated to every client inside of the `Returnable` so they can update the "Points" and "Timer" labels and the scoreboard.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MatchState {
+    pub phase: MatchPhase,
+    /// Seconds left on the current phase.
+    pub remaining: f32,
+    pub scores: HashMap<String, u32>,
+}
+
+impl MatchState {
+    pub const MIN_PLAYERS: usize = 2;
+    pub const WARMUP_TIME: f32 = 10.0;
+    pub const ROUND_TIME: f32 = 180.0;
+    pub const ROUND_END_TIME: f32 = 3.0;
+    pub const SCOREBOARD_TIME: f32 = 7.0;
+
+    pub fn new() -> Self {
+        Self {
+            phase: MatchPhase::Warmup,
+            remaining: Self::WARMUP_TIME,
+            scores: HashMap::new(),
+        }
+    }
+
+    /// # Update
+    /// Advances the countdown of the current phase and moves to the next one when it reaches zero.
+    ///
+    /// ## Params:
+    /// - delta_time: Seconds since the last server tick.
+    /// - player_count: Amount of players currently on the server.
+    pub fn update(&mut self, delta_time: f32, player_count: usize) {
+        if self.phase == MatchPhase::Warmup && player_count < Self::MIN_PLAYERS {
+            // the warmup waits until there are enough players to start
+            self.remaining = Self::WARMUP_TIME;
+            return;
+        }
+
+        self.remaining -= delta_time;
+
+        if self.remaining <= 0.0 {
+            match self.phase {
+                MatchPhase::Warmup => {
+                    for score in self.scores.values_mut() {
+                        *score = 0;
+                    }
+                    self.phase = MatchPhase::InProgress;
+                    self.remaining = Self::ROUND_TIME;
+                },
+                MatchPhase::InProgress => {
+                    self.phase = MatchPhase::RoundEnd;
+                    self.remaining = Self::ROUND_END_TIME;
+                },
+                MatchPhase::RoundEnd => {
+                    self.phase = MatchPhase::Scoreboard;
+                    self.remaining = Self::SCOREBOARD_TIME;
+                },
+                MatchPhase::Scoreboard => {
+                    self.phase = MatchPhase::Warmup;
+                    self.remaining = Self::WARMUP_TIME;
+                },
+            }
+        }
+    }
+
+    /// Adds a player to the score table, if it's already there nothing changes.
+    pub fn add_player(&mut self, id: &str) {
+        self.scores.entry(id.to_string()).or_insert(0);
+    }
+
+    pub fn remove_player(&mut self, id: &str) {
+        self.scores.remove(id);
+    }
+
+    /// Gives points to a player, points are only counted while the round is in progress.
+    pub fn award_points(&mut self, id: &str, points: u32) {
+        if self.phase == MatchPhase::InProgress {
+            *self.scores.entry(id.to_string()).or_insert(0) += points;
+        }
+    }
+
+    /// Players can only shoot while the match is on warmup or in progress.
+    pub fn can_fire(&self) -> bool {
+        matches!(self.phase, MatchPhase::Warmup | MatchPhase::InProgress)
+    }
+
+    pub fn score_of(&self, id: &str) -> u32 {
+        *self.scores.get(id).unwrap_or(&0)
+    }
+
+    /// Returns the scores sorted from the highest to the lowest.
+    pub fn ranking(&self) -> Vec<(String, u32)> {
+        let mut ranking: Vec<(String, u32)> = self.scores.iter().map(|(id, score)| (id.clone(), *score)).collect();
+        ranking.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranking
+    }
+
+    /// Text shown on the "Timer" label, for example `Round 2:59`.
+    pub fn timer_text(&self) -> String {
+        let seconds = self.remaining.max(0.0).ceil() as u32;
+        let phase = match self.phase {
+            MatchPhase::Warmup => "Warmup",
+            MatchPhase::InProgress => "Round",
+            MatchPhase::RoundEnd => "Round over",
+            MatchPhase::Scoreboard => "Next match in",
+        };
+
+        format!("{} {}:{:02}", phase, seconds / 60, seconds % 60)
+    }
+}
@@ -0,0 +1,431 @@
+use sdl2::{event::Event, keyboard::Keycode, pixels::Color};
+
+use crate::{app::{App, AppState, GameState}, engine::{assets::Assets, audio::{Audio, VolumeChannel}, display::{self, WindowMode}, log}, game_object::GameObject, input::{bindings::{Action, Bindings}, button_module::{Button, TextAlign}}, ui::{layout::{Anchor, Layout, Length, Stack}, manager::UiManager, text_cache::TextCache}};
+
+/// Creates one of the buttons used by the menus, all of them share the same colors (the size comes from [`menu_stack`]).
+pub fn menu_button(text: &str) -> Button {
+    Button::new(GameObject { active: true, x: 0.0, y: 0.0, width: 0.0, height: 0.0 }, Some(String::from(text)), Color::RGB(70, 70, 80), Color::WHITE, Color::RGB(100, 100, 130), Color::RGB(60, 140, 60), None, TextAlign::Center)
+}
+
+/// Creates a text without background, it's a button that is never clicked.
+pub fn menu_label(text: &str) -> Button {
+    Button::new(GameObject { active: true, x: 0.0, y: 0.0, width: 0.0, height: 0.0 }, Some(String::from(text)), Color::RGBA(0, 0, 0, 0), Color::WHITE, Color::RGBA(0, 0, 0, 0), Color::RGBA(0, 0, 0, 0), None, TextAlign::Center)
+}
+
+/// Column of menu buttons centered on the window, `y` moves the center of the first one from the center of the window.
+pub fn menu_stack(y: f32) -> Stack {
+    Stack::new(Layout::pixels(Anchor::Center, 240.0, 44.0).offset(0.0, y), 16.0)
+}
+
+/// Same as [`menu_stack`] moved `x` pixels from the center, for the menus with two columns.
+pub fn menu_column(x: f32, y: f32) -> Stack {
+    Stack::new(Layout::pixels(Anchor::Center, 240.0, 44.0).offset(x, y), 16.0)
+}
+
+/// A line of text as wide as the window, `y` moves its center from the center of the window.
+pub fn label_place(y: f32) -> Layout {
+    Layout::new(Anchor::Center, Length::Percent(100.0), Length::Pixels(30.0)).offset(0.0, y)
+}
+
+#[derive(Clone, Copy)]
+enum MainMenuAction {
+    Host,
+    Join,
+    Settings,
+    Quit,
+}
+
+/// # Main Menu
+/// First screen of the game, from here the player can host a game, join one, change the settings or quit.
+pub struct MainMenu {
+    // the title uses the pixel font, so it's not part of the ui manager
+    title: Button,
+    ui: UiManager<MainMenuAction>,
+}
+
+impl MainMenu {
+    pub fn new() -> Self {
+        let buttons = menu_stack(-78.0);
+
+        let mut ui = UiManager::new();
+        ui.add_button(buttons.item(0), menu_button("Host"), MainMenuAction::Host);
+        ui.add_button(buttons.item(1), menu_button("Join"), MainMenuAction::Join);
+        ui.add_button(buttons.item(2), menu_button("Settings"), MainMenuAction::Settings);
+        ui.add_button(buttons.item(3), menu_button("Quit"), MainMenuAction::Quit);
+
+        Self { title: menu_label("Arrownier"), ui }
+    }
+
+    pub fn update(&mut self, assets: &Assets, text_cache: &mut TextCache, app_state: &mut AppState, event_pump: &mut sdl2::EventPump, app: &mut App) {
+        let font = assets.text_font();
+        let mut clicked = None;
+        let window = app.canvas.output_size().unwrap_or((1280, 720));
+        if self.ui.layout(window) {
+            self.title.game_object = label_place(-175.0).resolve(window);
+        }
+
+        for event in event_pump.poll_iter() {
+            app.gamepads.handle_event(&event);
+            match event {
+                Event::Quit { .. } | Event::KeyDown { keycode: Some(Keycode::Escape), .. } => app_state.is_running = false,
+                _ => {}
+            }
+
+            if let Some(action) = self.ui.handle_event(&event) {
+                clicked = Some(action);
+            }
+        }
+
+        if let Some(action) = clicked {
+            self.ui.clear_focus();
+
+            match action {
+                MainMenuAction::Host => {
+                    let name = app.preferred_name.clone();
+                    match app.host(&name) {
+                        Ok(_) => app_state.state = GameState::Connecting,
+                        Err(err) => app_state.disconnected(&err),
+                    }
+                },
+                MainMenuAction::Join => app_state.state = GameState::Connecting,
+                MainMenuAction::Settings => app_state.state = GameState::Settings,
+                MainMenuAction::Quit => app_state.is_running = false,
+            }
+        }
+
+        self.title.render(&mut app.canvas, text_cache, assets.title_font());
+        self.ui.render(&mut app.canvas, text_cache, font);
+    }
+}
+
+#[derive(Clone, Copy)]
+enum SettingsAction {
+    Framerate,
+    Mode,
+    Resolution,
+    Vsync,
+    FrameCap,
+    Renderer,
+    Volume(VolumeChannel),
+    Controls,
+    Back,
+}
+
+/// # Settings Menu
+/// Options of the game that can be changed from the main menu, the video ones on the left and the sound and controls on the right.
+///
+/// The mode and the resolution are applied right away, the vsync and the renderer when the game starts again.
+pub struct SettingsMenu {
+    ui: UiManager<SettingsAction>,
+    framerate: usize,
+    mode: usize,
+    resolution: usize,
+    vsync: usize,
+    frame_cap: usize,
+    renderer: usize,
+    // ids of the volume buttons, with the channel each one changes
+    volumes: Vec<(VolumeChannel, usize)>,
+    notice: usize,
+    resolutions: Vec<(u32, u32)>,
+    renderers: Vec<String>,
+}
+
+impl SettingsMenu {
+    // how much each click changes a volume, after the full volume it goes back to silent
+    const VOLUME_STEP: f32 = 0.25;
+    // options of the frame cap, 0 is no limit
+    const FRAME_CAPS: [u32; 6] = [0, 30, 60, 120, 144, 240];
+    // distance from the center of the window to the center of each column
+    const COLUMN_OFFSET: f32 = 130.0;
+
+    pub fn new(app: &mut App) -> Self {
+        let video = menu_column(-Self::COLUMN_OFFSET, -150.0);
+        let sound = menu_column(Self::COLUMN_OFFSET, -150.0);
+
+        let mut framerate = menu_button("Show FPS");
+        framerate.toggle = Some(app.video.show_framerate);
+        let mut vsync = menu_button("VSync");
+        vsync.toggle = Some(app.video.vsync);
+
+        let mut ui = UiManager::new();
+        ui.add_label(label_place(-215.0), menu_label("Settings"));
+        let framerate = ui.add_button(video.item(0), framerate, SettingsAction::Framerate);
+        let mode = ui.add_button(video.item(1), menu_button(&Self::mode_text(app)), SettingsAction::Mode);
+        let resolution = ui.add_button(video.item(2), menu_button(&Self::resolution_text(app)), SettingsAction::Resolution);
+        let vsync = ui.add_button(video.item(3), vsync, SettingsAction::Vsync);
+        let frame_cap = ui.add_button(video.item(4), menu_button(&Self::frame_cap_text(app)), SettingsAction::FrameCap);
+        let renderer = ui.add_button(video.item(5), menu_button(&Self::renderer_text(app)), SettingsAction::Renderer);
+
+        let mut volumes = Vec::new();
+        for (index, channel) in [VolumeChannel::Master, VolumeChannel::Sfx, VolumeChannel::Music].into_iter().enumerate() {
+            let button = menu_button(&Self::volume_text(channel, &app.audio));
+            volumes.push((channel, ui.add_button(sound.item(index), button, SettingsAction::Volume(channel))));
+        }
+        ui.add_button(sound.item(3), menu_button("Controls"), SettingsAction::Controls);
+        ui.add_button(sound.item(5), menu_button("Back"), SettingsAction::Back);
+        let notice = ui.add_label(label_place(215.0), menu_label(""));
+
+        let window = app.canvas.window();
+        let display = window.display_index().unwrap_or(0);
+        let resolutions = display::resolutions(window.subsystem(), display, (app.video.width, app.video.height));
+
+        Self { ui, framerate, mode, resolution, vsync, frame_cap, renderer, volumes, notice, resolutions, renderers: display::renderers() }
+    }
+
+    fn mode_text(app: &App) -> String {
+        format!("Mode: {}", app.video.mode.name())
+    }
+
+    fn resolution_text(app: &App) -> String {
+        format!("Resolution: {}x{}", app.video.width, app.video.height)
+    }
+
+    fn frame_cap_text(app: &App) -> String {
+        match app.video.frame_cap {
+            0 => String::from("Frame cap: off"),
+            frame_cap => format!("Frame cap: {}", frame_cap),
+        }
+    }
+
+    fn renderer_text(app: &App) -> String {
+        format!("Renderer: {}", app.video.renderer)
+    }
+
+    fn volume_text(channel: VolumeChannel, audio: &Audio) -> String {
+        let name = match channel {
+            VolumeChannel::Master => "Volume",
+            VolumeChannel::Sfx => "Effects",
+            VolumeChannel::Music => "Music",
+        };
+        format!("{}: {}%", name, (audio.volumes().get(channel) * 100.0).round())
+    }
+
+    // the option after `current` on `options`, an option that is not on the list goes to the first one
+    fn next<T: PartialEq + Clone>(options: &[T], current: &T) -> T {
+        let index = options.iter().position(|option| option == current).map(|index| index + 1).unwrap_or(0);
+        options[index % options.len()].clone()
+    }
+
+    fn set_notice(&mut self, text: &str) {
+        self.ui.get_mut(self.notice).text = Some(String::from(text));
+    }
+
+    // changes the window and shows the error if the display didn't accept it
+    fn apply_video(&mut self, app: &mut App) {
+        match app.apply_video() {
+            Ok(_) => self.set_notice(""),
+            Err(err) => self.set_notice(&err),
+        }
+    }
+
+    pub fn update(&mut self, assets: &Assets, text_cache: &mut TextCache, app_state: &mut AppState, event_pump: &mut sdl2::EventPump, app: &mut App) {
+        let font = assets.text_font();
+        let mut clicked = None;
+        self.ui.layout(app.canvas.output_size().unwrap_or((1280, 720)));
+
+        for event in event_pump.poll_iter() {
+            app.gamepads.handle_event(&event);
+            match event {
+                Event::Quit { .. } => app_state.is_running = false,
+                Event::KeyDown { keycode: Some(Keycode::Escape), .. } => clicked = Some(SettingsAction::Back),
+                _ => {}
+            }
+
+            if let Some(action) = self.ui.handle_event(&event) {
+                clicked = Some(action);
+            }
+        }
+
+        // a window resized by hand changes the resolution too
+        self.ui.get_mut(self.resolution).text = Some(Self::resolution_text(app));
+
+        match clicked {
+            Some(SettingsAction::Framerate) => {
+                app.video.show_framerate = !app.video.show_framerate;
+                self.ui.get_mut(self.framerate).toggle = Some(app.video.show_framerate);
+            },
+            Some(SettingsAction::Mode) => {
+                app.video.mode = app.video.mode.next();
+                self.ui.get_mut(self.mode).text = Some(Self::mode_text(app));
+                self.apply_video(app);
+            },
+            Some(SettingsAction::Resolution) => {
+                let (width, height) = Self::next(&self.resolutions, &(app.video.width, app.video.height));
+                app.video.width = width;
+                app.video.height = height;
+                self.ui.get_mut(self.resolution).text = Some(Self::resolution_text(app));
+                // the borderless window always covers the display, the resolution is used when the mode changes
+                if app.video.mode != WindowMode::Borderless {
+                    self.apply_video(app);
+                }
+            },
+            Some(SettingsAction::Vsync) => {
+                app.video.vsync = !app.video.vsync;
+                self.ui.get_mut(self.vsync).toggle = Some(app.video.vsync);
+                self.set_notice("The vsync changes when the game starts again");
+            },
+            Some(SettingsAction::FrameCap) => {
+                app.video.frame_cap = Self::next(&Self::FRAME_CAPS, &app.video.frame_cap);
+                self.ui.get_mut(self.frame_cap).text = Some(Self::frame_cap_text(app));
+            },
+            Some(SettingsAction::Renderer) => {
+                app.video.renderer = Self::next(&self.renderers, &app.video.renderer);
+                self.ui.get_mut(self.renderer).text = Some(Self::renderer_text(app));
+                self.set_notice("The renderer changes when the game starts again");
+            },
+            Some(SettingsAction::Volume(channel)) => {
+                let volume = app.audio.volumes().get(channel);
+                let volume = if volume >= 1.0 { 0.0 } else { volume + Self::VOLUME_STEP };
+                app.audio.set_volume(channel, volume);
+
+                for (button_channel, id) in &self.volumes {
+                    if *button_channel == channel {
+                        self.ui.get_mut(*id).text = Some(Self::volume_text(channel, &app.audio));
+                    }
+                }
+            },
+            Some(SettingsAction::Controls) => {
+                self.ui.clear_focus();
+                app_state.state = GameState::Controls;
+            },
+            Some(SettingsAction::Back) => {
+                self.ui.clear_focus();
+                self.set_notice("");
+                app_state.state = GameState::Menu;
+            },
+            None => {}
+        }
+
+        // every change is saved right away
+        if matches!(clicked, Some(action) if !matches!(action, SettingsAction::Controls | SettingsAction::Back)) {
+            if let Err(err) = app.save_settings() {
+                log::error!("{}", err);
+            }
+        }
+
+        self.ui.render(&mut app.canvas, text_cache, font);
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ControlsAction {
+    Rebind(Action),
+    Reset,
+    Back,
+}
+
+/// # Controls Menu
+/// Shows the keys and gamepad buttons of each action of the gameplay, clicking an action waits for a key or button to add to it.
+///
+/// While waiting, escape cancels and backspace removes every key of the action. The bindings are saved on each change.
+pub struct ControlsMenu {
+    ui: UiManager<ControlsAction>,
+    // ids of the action buttons, on the same order as `Action::ALL`
+    rows: Vec<usize>,
+    // the action waiting for a key
+    listening: Option<Action>,
+    error: usize,
+}
+
+impl ControlsMenu {
+    pub fn new(app: &mut App) -> Self {
+        // there are many rows, so the buttons are wider and closer than the other menus
+        let buttons = Stack::new(Layout::pixels(Anchor::Center, 360.0, 32.0).offset(0.0, -190.0), 6.0);
+
+        let mut ui = UiManager::new();
+        ui.add_label(label_place(-235.0), menu_label("Controls"));
+
+        let mut rows = Vec::new();
+        for (index, action) in Action::ALL.into_iter().enumerate() {
+            rows.push(ui.add_button(buttons.item(index), menu_button(&Self::row_text(action, &app.bindings)), ControlsAction::Rebind(action)));
+        }
+        ui.add_button(buttons.item(Action::ALL.len()), menu_button("Reset"), ControlsAction::Reset);
+        ui.add_button(buttons.item(Action::ALL.len() + 1), menu_button("Back"), ControlsAction::Back);
+        let error = ui.add_label(label_place(240.0), menu_label(""));
+
+        Self { ui, rows, listening: None, error }
+    }
+
+    fn row_text(action: Action, bindings: &Bindings) -> String {
+        format!("{}: {}", action.name(), bindings.text(action))
+    }
+
+    fn refresh(&mut self, bindings: &Bindings) {
+        for (action, id) in Action::ALL.into_iter().zip(&self.rows) {
+            let text = match self.listening {
+                Some(listening) if listening == action => format!("{}: press a key or button...", action.name()),
+                _ => Self::row_text(action, bindings),
+            };
+            self.ui.get_mut(*id).text = Some(text);
+        }
+    }
+
+    // called after every change, if the file can't be written the error is shown under the buttons
+    fn save(&mut self, app: &App) {
+        self.refresh(&app.bindings);
+        self.ui.get_mut(self.error).text = app.save_settings().err();
+    }
+
+    pub fn update(&mut self, assets: &Assets, text_cache: &mut TextCache, app_state: &mut AppState, event_pump: &mut sdl2::EventPump, app: &mut App) {
+        let font = assets.text_font();
+        let mut clicked = None;
+        self.ui.layout(app.canvas.output_size().unwrap_or((1280, 720)));
+
+        for event in event_pump.poll_iter() {
+            app.gamepads.handle_event(&event);
+            if let Event::Quit { .. } = event {
+                app_state.is_running = false;
+                continue;
+            }
+
+            // while waiting for a key the keyboard doesn't move through the menu
+            if let Some(action) = self.listening {
+                if let Event::ControllerButtonDown { button, .. } = event {
+                    self.listening = None;
+                    app.bindings.bind_button(action, button);
+                    self.save(app);
+                }
+                if let Event::KeyDown { keycode: Some(key), repeat: false, .. } = event {
+                    self.listening = None;
+                    match key {
+                        Keycode::Escape => self.refresh(&app.bindings),
+                        Keycode::Backspace => {
+                            app.bindings.clear(action);
+                            self.save(app);
+                        },
+                        _ => {
+                            app.bindings.bind(action, key);
+                            self.save(app);
+                        },
+                    }
+                }
+                continue;
+            }
+
+            if let Event::KeyDown { keycode: Some(Keycode::Escape), .. } = event {
+                clicked = Some(ControlsAction::Back);
+            }
+            if let Some(action) = self.ui.handle_event(&event) {
+                clicked = Some(action);
+            }
+        }
+
+        match clicked {
+            Some(ControlsAction::Rebind(action)) => {
+                self.listening = Some(action);
+                self.refresh(&app.bindings);
+            },
+            Some(ControlsAction::Reset) => {
+                app.bindings = Bindings::default();
+                self.save(app);
+            },
+            Some(ControlsAction::Back) => {
+                self.ui.clear_focus();
+                app_state.state = GameState::Settings;
+            },
+            None => {}
+        }
+
+        self.ui.render(&mut app.canvas, text_cache, font);
+    }
+}
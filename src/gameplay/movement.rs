@@ -0,0 +1,80 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::assets::asset_path;
+
+use super::server_game_logic::Position;
+
+/// # Movement Config
+/// Tunables of the player movement, they are loaded by the server from a json file so they can be changed without compiling again.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct MovementConfig {
+    /// Pixels per second gained each second while a direction is pressed.
+    pub acceleration: f32,
+    /// Pixels per second lost each second while no direction is pressed.
+    pub friction: f32,
+    /// Max pixels per second a player can move.
+    pub max_speed: f32,
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        Self {
+            acceleration: 1600.0,
+            friction: 1200.0,
+            max_speed: 200.0,
+        }
+    }
+}
+
+impl MovementConfig {
+    pub const DEFAULT_PATH: &'static str = "assets/config/movement.json";
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let path = asset_path(path);
+        let content = fs::read_to_string(&path).map_err(|err| format!("The movement config {} couldn't be read: {}", path.display(), err))?;
+        let config: MovementConfig = serde_json::from_str(&content).map_err(|err| format!("The movement config {} is not valid: {}", path.display(), err))?;
+
+        if config.acceleration < 0.0 || config.friction < 0.0 || config.max_speed < 0.0 {
+            return Err(format!("The movement config {} can't have negative values", path.display()));
+        }
+
+        Ok(config)
+    }
+
+    /// # Apply
+    /// Calculates the new velocity of a player based on the direction it wants to move to.
+    ///
+    /// ## Params:
+    /// - velocity: Current velocity of the player.
+    /// - direction: Input direction, it gets normalized so moving on diagonals is not faster. A length under 1 (from an analog stick) lowers the max speed by the same amount.
+    /// - delta_time: Seconds since the last server tick.
+    ///
+    /// ## Returns:
+    /// - The new velocity, it never goes over `max_speed`.
+    pub fn apply(&self, velocity: Position, direction: Position, delta_time: f32) -> Position {
+        let strength = direction.length().min(1.0);
+        let max_speed = if strength > 0.0 { self.max_speed * strength } else { self.max_speed };
+        let direction = direction.normalized();
+        let mut velocity = velocity;
+
+        if direction.length() > 0.0 {
+            velocity.x += direction.x * self.acceleration * delta_time;
+            velocity.y += direction.y * self.acceleration * delta_time;
+        } else {
+            // friction slows the player down until it stops, it never makes it go backwards
+            let speed = velocity.length();
+            let new_speed = (speed - self.friction * delta_time).max(0.0);
+            velocity = velocity.normalized().scaled(new_speed);
+        }
+
+        let speed = velocity.length();
+        if speed > max_speed {
+            velocity = velocity.normalized().scaled(max_speed);
+        }
+
+        velocity
+    }
+}
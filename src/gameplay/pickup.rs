@@ -0,0 +1,97 @@
+use sdl2::{pixels::Color, rect::Rect, render::Canvas, video::Window};
+use serde::{Deserialize, Serialize};
+
+use crate::{engine::camera::Camera, game_object::GameObject};
+
+use super::server_game_logic::Position;
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+pub enum PickupKind {
+    /// Gives points to the player, only while the round is in progress.
+    Points,
+    /// Heals the player.
+    Health,
+    /// Gives arrows back to the player.
+    Arrows,
+}
+
+/// # Pickup Spawn
+/// A pickup defined on the map file, without a position it appears on a random place of the map each time it respawns.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PickupSpawn {
+    pub kind: PickupKind,
+    #[serde(default)]
+    pub position: Option<Position>,
+    /// Seconds until the pickup appears again after being collected.
+    #[serde(default = "PickupSpawn::default_respawn_time")]
+    pub respawn_time: f32,
+}
+
+impl PickupSpawn {
+    fn default_respawn_time() -> f32 {
+        10.0
+    }
+}
+
+/// # Pickup
+/// A pickup on the world, the server replicates all of them to the clients, even the ones that are waiting to respawn.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Pickup {
+    pub kind: PickupKind,
+    pub position: Position,
+    pub active: bool,
+    /// Seconds left until it's active again, only used while it's not active.
+    pub respawn_in: f32,
+}
+
+impl Pickup {
+    pub const SIZE: f32 = 20.0;
+
+    pub fn game_object(&self) -> GameObject {
+        GameObject {
+            active: self.active,
+            x: self.position.x,
+            y: self.position.y,
+            width: Self::SIZE,
+            height: Self::SIZE,
+        }
+    }
+
+    /// Each kind of pickup is drawn with its own shape, so they can be told apart without reading anything.
+    pub fn render(&self, canvas: &mut Canvas<Window>, camera: &Camera) {
+        if !self.active {
+            return;
+        }
+
+        let rect = camera.rect(&self.game_object());
+        let x = rect.x();
+        let y = rect.y();
+        let size = rect.width() as i32;
+
+        match self.kind {
+            PickupKind::Points => {
+                // a coin, a square with a smaller one inside
+                canvas.set_draw_color(Color::RGB(230, 190, 40));
+                canvas.fill_rect(Rect::new(x, y, size as u32, size as u32)).unwrap();
+                canvas.set_draw_color(Color::RGB(255, 230, 120));
+                canvas.fill_rect(Rect::new(x + size / 4, y + size / 4, (size / 2) as u32, (size / 2) as u32)).unwrap();
+            },
+            PickupKind::Health => {
+                // a cross
+                let thickness = size / 3;
+                canvas.set_draw_color(Color::RGB(220, 50, 60));
+                canvas.fill_rect(Rect::new(x + thickness, y, thickness as u32, size as u32)).unwrap();
+                canvas.fill_rect(Rect::new(x, y + thickness, size as u32, thickness as u32)).unwrap();
+            },
+            PickupKind::Arrows => {
+                // a bundle of three arrows
+                canvas.set_draw_color(Color::RGB(160, 110, 60));
+                for offset in [2, size / 2 - 1, size - 4] {
+                    canvas.fill_rect(Rect::new(x + offset, y + 4, 2, (size - 4) as u32)).unwrap();
+                }
+                canvas.set_draw_color(Color::RGB(230, 200, 80));
+                canvas.fill_rect(Rect::new(x, y, size as u32, 4)).unwrap();
+            },
+        }
+    }
+}
@@ -1,59 +1,126 @@
 use std::{collections::HashMap, net::{IpAddr, SocketAddr}, time::{Duration, Instant}};
 
-use rand::{distributions::Alphanumeric, Rng};
-use sdl2::{event::Event, keyboard::Keycode, pixels::Color, rect::Rect, ttf::Font};
+use sdl2::{clipboard::ClipboardUtil, event::Event, keyboard::Keycode, mouse::MouseButton, pixels::Color, rect::Rect, render::Canvas, ttf::Font, video::Window};
 use serde::{Deserialize, Serialize};
-use crate::{app::{App, AppState}, game_object::GameObject, gameplay::server_game_logic::Position, input::button_module::{Button, TextAlign}};
+use crate::{app::{App, AppState, GameState}, engine::{assets::Assets, audio::Sound, camera::Camera, sprite::{Animator, DrawParams}}, game_object::GameObject, gameplay::{map::MapData, match_state::{MatchPhase, MatchState}, server_game_logic::{Position, Returnable, ServerGameLogic}}, input::{bindings::{Action, Bindings}, button_module::{Button, TextAlign}, gamepad::Gamepads}, networking::protocol::{ChatMessage, ClientMessage}, ui::{chat::ChatBox, layout::{Anchor, Layout, Stack}, manager::UiManager, text_cache::TextCache}};
 
 pub struct GameLogic { // here we define the data we use on our script
     last_frame: Instant,
     pub start_time: Instant,
-    ui_elements: Vec<Button>,
+    hud: UiManager<()>,
     frame_count: u32,
     frame_timer: Duration,
     fps: u32,
     controls: Controls,
+    // keys held right now, the controls come from them and the bindings
+    held_keys: Vec<Keycode>,
+    // the left click fires too
+    mouse_fire: bool,
+    // the aim is only sent after the mouse moves on the match, before that the player looks to where it moves
+    mouse_aim: bool,
     send_packet: Instant,
+    // the ready message is a single datagram, so it's sent again until the server spawns us
+    last_ready: Instant,
     
     // networking
     pub last_packet_sent: Option<Packet>,
     pub instance_id: String, // this value is for id-ing the client instance
-    players: HashMap<String, GameObject>
+    players: HashMap<String, GameObject>,
+    // the world is drawn from the point of view of the camera, it follows the local player
+    camera: Camera,
+    show_scoreboard: bool,
+    switch_team: bool,
+    chat: ChatBox,
+    // animation of each player, by id
+    animators: HashMap<String, Animator>,
+    // hits and arrows of each player on the last snapshot, the sounds are played when they change
+    last_seen: HashMap<String, (u32, u32)>,
+    // chat texts written on this frame, they are sent on the update
+    pending_chat: Vec<String>,
+    // the screenshot key was pressed, the app saves the frame
+    screenshot: bool,
+    // the network stats key was pressed, the overlay is shown or hidden
+    toggle_net_stats: bool
 
 } 
 
-#[derive(Serialize, Deserialize, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Default)]
 pub struct Controls {
     pub left: bool,
     pub right: bool,
     pub up: bool,
-    pub down: bool
+    pub down: bool,
+    pub fire: bool,
+    /// Analog movement of a gamepad stick, each axis goes from -1 to 1 and the length is how fast the player wants to move.
+    /// It's zero while the stick is not used, then the direction comes from the buttons above.
+    #[serde(default)]
+    pub stick: Position,
+    /// Direction the player is aiming to, as a step of [`Controls::AIM_STEPS`] going clockwise from the right.
+    /// It's `None` until the mouse is used, then the player looks to where it moves.
+    #[serde(default)]
+    pub aim: Option<u16>,
+}
+
+impl Controls {
+    /// Steps of a full turn the aim is sent with, it's precise enough and doesn't send a packet with each pixel the mouse moves.
+    pub const AIM_STEPS: u16 = 256;
+
+    /// Converts an angle in radians (clockwise from the right, as y goes down) to the nearest aim step.
+    pub fn quantize_aim(angle: f32) -> u16 {
+        let turn = angle.rem_euclid(std::f32::consts::TAU) / std::f32::consts::TAU;
+        (turn * Self::AIM_STEPS as f32).round() as u16 % Self::AIM_STEPS
+    }
+
+    /// The aim as a direction with a length of 1.
+    pub fn aim_direction(&self) -> Option<Position> {
+        self.aim.map(|step| {
+            let angle = (step % Self::AIM_STEPS) as f32 / Self::AIM_STEPS as f32 * std::f32::consts::TAU;
+            Position { x: angle.cos(), y: angle.sin() }
+        })
+    }
 }
 
-#[derive(Serialize, Deserialize, PartialEq)]
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
 pub struct Packet {
     pub controls: Controls,
     pub id: String
 }
 
 impl GameLogic {
+    // ids of the hud labels
+    const POINTS: usize = 0;
+    const TIMER: usize = 1;
+    const FRAMERATE: usize = 2;
+    const QUIVER: usize = 3;
+    // the arrow sprite is small, so it's drawn bigger than the sheet
+    const ARROW_SCALE: u32 = 2;
+    // how much the zoom changes with each press of + or -
+    const ZOOM_STEP: f32 = 0.25;
+    // the stick is sent with this many steps per unit
+    const STICK_STEPS: f32 = 16.0;
+    // time between the ready messages while the local player is not on the snapshots
+    const READY_RESEND: Duration = Duration::from_millis(500);
+
     // this is called once
     pub fn new(app: &mut App) -> Self {
         // UI ELEMENT
-        let ui_points = Button::new(GameObject { active: true, x:((app.width/2) - 70 ) as f32, y: 10.0, width: 140.0, height: 30.0}, Some(String::from("Points")),Color::RGB(200, 100, 100), Color::WHITE, Color::RGB(200, 10, 0), Color::RGB(200, 0, 0),None, TextAlign::Center);
+        let ui_points = Button::new(GameObject { active: true, x: 0.0, y: 0.0, width: 140.0, height: 30.0}, Some(String::from("Points")),Color::RGB(200, 100, 100), Color::WHITE, Color::RGB(200, 10, 0), Color::RGB(200, 0, 0),None, TextAlign::Center);
         let timer = Button::new(GameObject {active: true, x:10 as f32, y: 30.0, width: 0.0, height: 0.0},Some(String::from("Timer")),Color::RGB(100, 100, 100),Color::WHITE,Color::RGB(0, 200, 0),Color::RGB(0, 0, 0),None, TextAlign::Left);
         let framerate = Button::new(GameObject {active: true, x:10 as f32, y: 10.0, width: 0.0, height: 0.0},Some(String::from("Framerate")),Color::RGBA(100, 100, 100, 0),Color::WHITE,Color::RGB(0, 200, 0),Color::RGB(0, 0, 0),None, TextAlign::Left);
+        let quiver = Button::new(GameObject {active: true, x:10 as f32, y: 50.0, width: 0.0, height: 0.0},Some(String::from("Arrows")),Color::RGBA(100, 100, 100, 0),Color::WHITE,Color::RGB(0, 200, 0),Color::RGB(0, 0, 0),None, TextAlign::Left);
 
         // UI LISTS
-        let ui_elements = vec![ui_points, timer, framerate];
+        // the hud only has labels, their ids are the POINTS, TIMER, FRAMERATE and QUIVER constants
+        // the points go centered at the top, the other texts are a column on the top left corner
+        let column = Stack::new(Layout::pixels(Anchor::TopLeft, 0.0, 0.0).padding(10.0), 20.0);
+        let mut hud = UiManager::new();
+        hud.add_label(Layout::pixels(Anchor::Top, 140.0, 30.0).padding(10.0), ui_points);
+        hud.add_label(column.item(1), timer);
+        hud.add_label(column.item(0), framerate);
+        hud.add_label(column.item(2), quiver);
 
-        // we will send this value to the server so we can make differenciation between the user instance and the other players
-        // this value should be added based entirely on the connection of a new game
-        let instance_id: String = rand::thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(10)
-            .map(char::from)
-            .collect();
+        // the id is created by the app when connecting, it's the one the server knows us by
+        let instance_id = app.instance_id.clone();
 
         Self {
             last_frame: Instant::now(),
@@ -61,24 +128,39 @@ impl GameLogic {
             frame_count: 0,
             frame_timer: Duration::new(0, 0),
             fps: 0,
-            ui_elements,
-            controls: Controls {
-                left: false,
-                right: false,
-                up: false,
-                down: false,
-            },
+            hud,
+            controls: Controls::default(),
+            held_keys: Vec::new(),
+            mouse_fire: false,
+            mouse_aim: false,
             send_packet: Instant::now(),
+            last_ready: Instant::now(),
             last_packet_sent: None,
             instance_id,
-            players: HashMap::new()
+            players: HashMap::new(),
+            camera: Camera::new(app.canvas.output_size().unwrap_or((1280, 720))),
+            show_scoreboard: false,
+            switch_team: false,
+            chat: ChatBox::new(GameObject { active: true, x: 10.0, y: 710.0, width: 500.0, height: 0.0 }),
+            pending_chat: Vec::new(),
+            animators: HashMap::new(),
+            last_seen: HashMap::new(),
+            screenshot: false,
+            toggle_net_stats: false
         }
     }
 
     // this is called every frame
-    pub fn update(&mut self, _font: &Font, mut app_state: &mut AppState, mut event_pump: &mut sdl2::EventPump, app: &mut App) {
+    pub fn update(&mut self, assets: &Assets, text_cache: &mut TextCache, mut app_state: &mut AppState, mut event_pump: &mut sdl2::EventPump, app: &mut App) {
+        let _font = assets.text_font();
         self.display_framerate(app);
-        self.ui_elements[2].render(&mut app.canvas, &app.texture_creator, _font);
+
+        // the chat is not part of the hud, so it's moved with it when the window changes
+        let window = app.canvas.output_size().unwrap_or((1280, 720));
+        if self.hud.layout(window) {
+            self.chat.place(Layout::pixels(Anchor::BottomLeft, 500.0, 0.0).padding(10.0).resolve(window));
+        }
+        self.hud.get_mut(Self::FRAMERATE).game_object.active = app.video.show_framerate;
 
         // create the packet to send
         let packet = Packet {
@@ -100,80 +182,271 @@ impl GameLogic {
             },
         }
 
+        // the first ready was sent from the lobby, but it could have been lost
+        let spawned = app.received.as_ref().is_some_and(|returned| returned.players_data.contains_key(&self.instance_id));
+        if !spawned && self.last_ready.elapsed() >= Self::READY_RESEND {
+            app.send(&ClientMessage::Ready);
+            self.last_ready = Instant::now();
+        }
+
+        // chat messages go through the reliable channel, the ones without acknowledge are sent again
+        for text in std::mem::take(&mut self.pending_chat) {
+            match app.chat_out.send(text.clone()) {
+                Some(sequence) => app.send(&ClientMessage::Chat { sequence, text }),
+                None => self.chat.push(ChatMessage { from: None, text: String::from("The message was not sent, the server is not answering") }),
+            }
+        }
+        for (sequence, text) in app.chat_out.due_for_resend() {
+            app.send(&ClientMessage::Chat { sequence, text });
+        }
+        for message in app.chat_inbox.drain(..) {
+            self.chat.push(message);
+        }
+
+        // the team change is asked from the team we currently are on the last snapshot
+        if self.switch_team {
+            self.switch_team = false;
+            let current_team = app.received.as_ref().and_then(|returned| returned.players_data.get(&self.instance_id)).map(|player| player.team);
+
+            if let Some(team) = current_team {
+                app.send(&ClientMessage::ChooseTeam(team.other()));
+            }
+        }
+
+        self.update_camera(app, window);
+        self.play_sounds(app);
+
+        // the map is drawn under everything else
+        if let Some(map) = &app.map {
+            app.canvas.set_draw_color(Color::RGB(70, 70, 80));
+            for wall in &map.walls {
+                app.canvas.fill_rect(self.camera.rect(&wall.game_object())).unwrap();
+            }
+        }
+
         match &app.received {
             Some(returned) => {
+                // pickups go under the players
+                for pickup in &returned.pickups {
+                    pickup.render(&mut app.canvas, &self.camera);
+                }
+
                 for (id, position) in &returned.players_data {
                     match self.players.get_mut(id) {
                         Some(player) => {
                             // if the player exists, just change the position of itself
+                            player.x = position.position.x;
+                            player.y = position.position.y;
+
+                            let animator = self.animators.entry(id.clone()).or_default();
+                            animator.play(if position.velocity.length() > 1.0 { "walk" } else { "idle" });
+                            animator.update(app.time.delta_time);
+
+                            let body = self.camera.rect(player);
+                            match assets.sprite_sheet("player") {
+                                // the sprite is white so it's tinted with the team color, it looks to the side the player is facing
+                                Some(sheet) => sheet.draw(&mut app.canvas, assets, body, DrawParams { frame: animator.frame(sheet), flip: position.facing.x < 0.0, tint: position.team.color(), ..Default::default() }),
+                                None => {
+                                    app.canvas.set_draw_color(position.team.color());
+                                    app.canvas.fill_rect(body).unwrap();
+                                },
+                            }
+
+                            // the local player gets an outline so it can be found between the players of its team
                             if *id == self.instance_id {
-                                app.canvas.set_draw_color(Color::RGB(100, 100, 200));
-                            } else {
-                                app.canvas.set_draw_color(Color::RGB(100, 100, 100));
+                                app.canvas.set_draw_color(Color::WHITE);
+                                app.canvas.draw_rect(body).unwrap();
                             }
 
-                            player.x = position.x;
-                            player.y = position.y;
-                            app.canvas.fill_rect(Rect::new(player.x as i32, player.y as i32, player.width as u32, player.height as u32)).unwrap();
+                            // name over the player, the health bar goes between the name and the player
+                            // the texts keep their size with any zoom, only their position follows the camera
+                            let name_label = Button::new(GameObject { active: true, x: body.x() as f32 - 40.0, y: body.y() as f32 - 32.0, width: body.width() as f32 + 80.0, height: 20.0 }, Some(position.name.clone()), Color::RGBA(0, 0, 0, 0), Color::WHITE, Color::RGBA(0, 0, 0, 0), Color::RGBA(0, 0, 0, 0), None, TextAlign::Center);
+                            name_label.render(&mut app.canvas, text_cache, _font);
+
+                            // health bar over the player
+                            let health_width = (body.width() as f32 * (position.health / ServerGameLogic::MAX_HEALTH)).max(0.0);
+                            app.canvas.set_draw_color(Color::RGB(200, 40, 40));
+                            app.canvas.fill_rect(Rect::new(body.x(), body.y() - 8, health_width as u32, 4)).unwrap();
                         },
                         None => {
                             // if the player dont exists, instance it in the map
                             self.players.insert(id.to_string(), GameObject {
                                 active: true,
-                                x: position.x,
-                                y: position.y,
-                                width: 40.0,
-                                height: 40.0,
+                                x: position.position.x,
+                                y: position.position.y,
+                                width: ServerGameLogic::PLAYER_SIZE,
+                                height: ServerGameLogic::PLAYER_SIZE,
                             });
                         },
                     }
 
                 }
+
+                if let Some(local_player) = returned.players_data.get(&self.instance_id) {
+                    self.hud.get_mut(Self::QUIVER).text = Some(format!("Arrows: {}/{}", local_player.arrows, ServerGameLogic::MAX_ARROWS));
+                }
+
+                // players that left don't need their animation anymore
+                self.animators.retain(|id, _| returned.players_data.contains_key(id));
+
+                match assets.sprite_sheet("arrow") {
+                    Some(sheet) => {
+                        let width = (sheet.frame_width * Self::ARROW_SCALE) as f32;
+                        let height = (sheet.frame_height * Self::ARROW_SCALE) as f32;
+                        for arrow in &returned.arrows {
+                            // the sprite points to the right, so it's rotated to the direction of the arrow
+                            let angle = arrow.direction.y.atan2(arrow.direction.x).to_degrees() as f64;
+                            let destination = self.camera.rect(&GameObject { active: true, x: arrow.position.x - width / 2.0, y: arrow.position.y - height / 2.0, width, height });
+                            sheet.draw(&mut app.canvas, assets, destination, DrawParams { angle, ..Default::default() });
+                        }
+                    },
+                    None => {
+                        app.canvas.set_draw_color(Color::RGB(230, 200, 80));
+                        for arrow in &returned.arrows {
+                            app.canvas.fill_rect(self.camera.rect(&GameObject { active: true, x: arrow.position.x - 3.0, y: arrow.position.y - 3.0, width: 6.0, height: 6.0 })).unwrap();
+                        }
+                    },
+                }
+
+                self.display_match(&returned.match_state);
             },
             None => {},
         }
 
-        Self::event_handler(self, &mut app_state, &mut event_pump);
+        self.hud.render(&mut app.canvas, text_cache, _font);
+
+        self.chat.render(&mut app.canvas, text_cache, _font);
+
+        if let Some(returned) = &app.received {
+            let phase = returned.match_state.phase;
+            if self.show_scoreboard || phase == MatchPhase::RoundEnd || phase == MatchPhase::Scoreboard {
+                self.render_scoreboard(returned, &mut app.canvas, text_cache, _font);
+            }
+        }
+
+        app.net_overlay.render(&app.stats, &mut app.canvas, text_cache, _font);
+
+        Self::event_handler(self, &mut app_state, &mut event_pump, &app.clipboard, &app.bindings, &mut app.gamepads);
+        if std::mem::take(&mut self.screenshot) {
+            app.screenshot_requested = true;
+        }
+        if std::mem::take(&mut self.toggle_net_stats) {
+            app.net_overlay.visible = !app.net_overlay.visible;
+        }
     }
 
-    fn event_handler(&mut self, app_state: &mut AppState, event_pump: &mut sdl2::EventPump) {
+    fn event_handler(&mut self, app_state: &mut AppState, event_pump: &mut sdl2::EventPump, clipboard: &ClipboardUtil, bindings: &Bindings, gamepads: &mut Gamepads) {
         for event in event_pump.poll_iter() {
+            gamepads.handle_event(&event);
+            if let Event::Quit { .. } = event {
+                app_state.is_running = false;
+                continue;
+            }
+
+            // while the chat is open it takes every key, so writing doesn't move the player
+            let chat_was_open = self.chat.open;
+            if let Some(text) = self.chat.handle_event(&event, clipboard) {
+                self.pending_chat.push(text);
+            }
+            if chat_was_open || self.chat.open {
+                self.held_keys.clear();
+                self.mouse_fire = false;
+                continue;
+            }
+
             match event {
-                sdl2::event::Event::KeyDown { keycode, .. } => {
-                    match keycode {
-                        Some(key) => {
-                            match key {
-                                Keycode::Up     => self.controls.up     = true,
-                                Keycode::Down   => self.controls.down   = true,
-                                Keycode::Left   => self.controls.left   = true,
-                                Keycode::Right  => self.controls.right  = true,
-                                
-                                _ => {}
-                            }
-                        },
-                        None => {},
+                Event::KeyDown { keycode: Some(key), repeat: false, .. } => {
+                    if !self.held_keys.contains(&key) {
+                        self.held_keys.push(key);
+                    }
+
+                    for action in bindings.actions(key) {
+                        match action {
+                            Action::Chat => {
+                                // the keys held until now are released, the chat takes the keyboard
+                                self.chat.open_input();
+                                self.held_keys.clear();
+                            },
+                            Action::Screenshot => self.screenshot = true,
+                            Action::NetStats => self.toggle_net_stats = true,
+                            _ => {}
+                        }
+                    }
+
+                    // the keys that are not actions are fixed
+                    match key {
+                        Keycode::T      => self.switch_team     = true,
+                        Keycode::Equals | Keycode::KpPlus   => self.camera.set_zoom(self.camera.zoom + Self::ZOOM_STEP),
+                        Keycode::Minus  | Keycode::KpMinus  => self.camera.set_zoom(self.camera.zoom - Self::ZOOM_STEP),
+                        // the app disconnects us when going back to the menu
+                        Keycode::Escape => app_state.state      = GameState::Menu,
+                        _ => {}
                     }
                 },
-                sdl2::event::Event::KeyUp { keycode, .. } => {
-                    match keycode {
-                        Some(key) => {
-                            match key {
-                                Keycode::Up     => self.controls.up     = false,
-                                Keycode::Down   => self.controls.down   = false,
-                                Keycode::Left   => self.controls.left   = false,
-                                Keycode::Right  => self.controls.right  = false,
-                                _ => {}
-                            }
-                        },
-                        None => {},
+                Event::KeyUp { keycode: Some(key), .. } => {
+                    self.held_keys.retain(|held| *held != key);
+                },
+                Event::MouseMotion { .. } => self.mouse_aim = true,
+                Event::MouseButtonDown { mouse_btn: MouseButton::Left, .. } => {
+                    self.mouse_aim = true;
+                    self.mouse_fire = true;
+                },
+                Event::MouseButtonUp { mouse_btn: MouseButton::Left, .. } => self.mouse_fire = false,
+                Event::ControllerButtonDown { button, .. } => {
+                    if bindings.buttons(Action::Chat).contains(&button) {
+                        self.chat.open_input();
+                        self.held_keys.clear();
+                    }
+                    if bindings.buttons(Action::Screenshot).contains(&button) {
+                        self.screenshot = true;
+                    }
+                    if bindings.buttons(Action::NetStats).contains(&button) {
+                        self.toggle_net_stats = true;
                     }
                 },
-                Event::Quit { .. } => {
-                    app_state.is_running = false;
-                } 
                 _ => {}
             }
         }
+
+        let mouse = event_pump.mouse_state();
+        self.update_controls(bindings, gamepads, (mouse.x(), mouse.y()));
+    }
+
+    /// # Update Controls
+    /// The controls are taken from every key and button held, so releasing one of two keys of the same action doesn't stop it.
+    ///
+    /// The stick is rounded to steps of [`GameLogic::STICK_STEPS`], small changes of the stick would send a packet each frame otherwise.
+    /// The aim goes from the center of the local player to the mouse, both on the world, so it works with any camera position and zoom.
+    fn update_controls(&mut self, bindings: &Bindings, gamepads: &Gamepads, mouse: (i32, i32)) {
+        if self.chat.open {
+            self.controls = Controls::default();
+            self.show_scoreboard = false;
+            return;
+        }
+
+        let held = |action| bindings.is_held(action, &self.held_keys) || gamepads.is_held(action, bindings);
+        let stick = gamepads.stick();
+
+        let local_player = self.players.get(&self.instance_id).filter(|_| self.mouse_aim);
+        let aim = local_player.map(|player| {
+            let (mouse_x, mouse_y) = self.camera.screen_to_world(mouse.0 as f32, mouse.1 as f32);
+            let angle = (mouse_y - (player.y + player.height / 2.0)).atan2(mouse_x - (player.x + player.width / 2.0));
+            Controls::quantize_aim(angle)
+        });
+
+        self.controls = Controls {
+            up: held(Action::MoveUp),
+            down: held(Action::MoveDown),
+            left: held(Action::MoveLeft),
+            right: held(Action::MoveRight),
+            fire: held(Action::Fire) || self.mouse_fire,
+            stick: Position {
+                x: (stick.x * Self::STICK_STEPS).round() / Self::STICK_STEPS,
+                y: (stick.y * Self::STICK_STEPS).round() / Self::STICK_STEPS,
+            },
+            aim,
+        };
+        self.show_scoreboard = held(Action::Scoreboard);
     }
 
 
@@ -181,7 +454,7 @@ impl GameLogic {
 
     // the controler packet will only be sent if there is a change on the controller struct
     fn send_packet(&mut self, app: &mut App, packet: &Packet) {
-        app.socket.send_to(serde_json::to_string(&packet).unwrap().as_bytes(), app.connect_to.to_owned()).unwrap();
+        app.send(&ClientMessage::Input(packet.clone()));
         self.send_packet = Instant::now()
     }
 
@@ -200,9 +473,113 @@ impl GameLogic {
     }
     */
 
+    /// # Update Camera
+    /// Moves the camera towards the center of the local player and keeps it inside the map.
+    ///
+    /// The first time the local player is seen the camera jumps to it, so it doesn't travel across the map when joining.
+    fn update_camera(&mut self, app: &App, window: (u32, u32)) {
+        self.camera.set_viewport(window);
+
+        let local_player = app.received.as_ref().and_then(|returned| returned.players_data.get(&self.instance_id));
+        if let Some(local_player) = local_player {
+            let target = Position {
+                x: local_player.position.x + ServerGameLogic::PLAYER_SIZE / 2.0,
+                y: local_player.position.y + ServerGameLogic::PLAYER_SIZE / 2.0,
+            };
+
+            if self.players.contains_key(&self.instance_id) {
+                self.camera.follow(target, app.time.delta_time);
+            } else {
+                self.camera.snap_to(target);
+            }
+        }
+
+        // the map is sent when joining, until then the size of the default one is used
+        let (world_width, world_height) = match &app.map {
+            Some(map) => (map.width, map.height),
+            None => {
+                let map = MapData::default();
+                (map.width, map.height)
+            },
+        };
+        self.camera.clamp(world_width, world_height);
+    }
+
+    /// # Play Sounds
+    /// The server doesn't send events, so the sounds come from comparing each player with the last snapshot:
+    /// less arrows is a fire, less health is a hit and a new player is a join.
+    fn play_sounds(&mut self, app: &App) {
+        let returned = match &app.received {
+            Some(returned) => returned,
+            None => return,
+        };
+
+        let mut sounds = Vec::new();
+        for (id, player) in &returned.players_data {
+            match self.last_seen.get(id) {
+                Some((hits, arrows)) => {
+                    if player.arrows < *arrows {
+                        sounds.push(Sound::Fire);
+                    }
+                    // the hits are counted by the server, the health can't be used since a kill respawns the player with all of it
+                    if player.hits > *hits {
+                        sounds.push(Sound::Hit);
+                    }
+                },
+                // the players that were already on the match when we joined don't sound
+                None if self.last_seen.is_empty() && *id != self.instance_id => {},
+                None => sounds.push(Sound::Join),
+            }
+        }
+
+        self.last_seen = returned.players_data.iter().map(|(id, player)| (id.clone(), (player.hits, player.arrows))).collect();
+
+        // many players firing on the same frame sound as one
+        let mut played = Vec::new();
+        for sound in sounds {
+            if played.contains(&sound) {
+                continue;
+            }
+            played.push(sound);
+            app.audio.play(sound);
+        }
+    }
+
     fn display_framerate(&mut self, app: &mut App) {
         // Render FPS text
         let fps_text = format!("FPS: {}", app.time.get_fps());
-        self.ui_elements[2].text = Some(fps_text);
+        self.hud.get_mut(Self::FRAMERATE).text = Some(fps_text);
+    }
+
+    fn display_match(&mut self, match_state: &MatchState) {
+        self.hud.get_mut(Self::POINTS).text = Some(format!("Points: {}", match_state.score_of(&self.instance_id)));
+        self.hud.get_mut(Self::TIMER).text = Some(match_state.timer_text());
+    }
+
+    // the scoreboard is rendered over everything else, it shows every player sorted by their points
+    fn render_scoreboard(&self, returned: &Returnable, canvas: &mut Canvas<Window>, text_cache: &mut TextCache, font: &Font) {
+        let ranking = returned.match_state.ranking();
+        let row_height = 30.0;
+        let board_width = 400.0;
+        let board_height = row_height * (ranking.len() + 1) as f32 + 20.0;
+        let (window_width, window_height) = canvas.output_size().unwrap_or((1280, 720));
+        let board_x = (window_width as f32 - board_width) / 2.0;
+        let board_y = (window_height as f32 - board_height) / 2.0;
+
+        canvas.set_draw_color(Color::RGBA(0, 0, 0, 200));
+        canvas.fill_rect(Rect::new(board_x as i32, board_y as i32, board_width as u32, board_height as u32)).unwrap();
+
+        let title = Button::new(GameObject { active: true, x: board_x, y: board_y + 10.0, width: board_width, height: row_height }, Some(String::from("Scoreboard")), Color::RGBA(0, 0, 0, 0), Color::WHITE, Color::RGBA(0, 0, 0, 0), Color::RGBA(0, 0, 0, 0), None, TextAlign::Center);
+        title.render(canvas, text_cache, font);
+
+        for (index, (id, score)) in ranking.iter().enumerate() {
+            let (name, text_color) = match returned.players_data.get(id) {
+                Some(player) => (player.name.as_str(), player.team.color()),
+                None => (id.as_str(), Color::GRAY),
+            };
+            let you = if *id == self.instance_id { " (you)" } else { "" };
+            let row = Button::new(GameObject { active: true, x: board_x + 20.0, y: board_y + 10.0 + row_height * (index + 1) as f32, width: 0.0, height: 0.0 }, Some(format!("{}. {}{}  -  {}", index + 1, name, you, score)), Color::RGBA(0, 0, 0, 0), text_color, Color::RGBA(0, 0, 0, 0), Color::RGBA(0, 0, 0, 0), None, TextAlign::Left);
+            row.render(canvas, text_cache, font);
+        }
     }
 }
\ No newline at end of file
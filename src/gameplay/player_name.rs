@@ -0,0 +1,81 @@
+/// Max amount of characters of a player name.
+pub const MAX_NAME_LENGTH: usize = 16;
+/// Name used when the one sent by the client has nothing usable.
+pub const DEFAULT_NAME: &str = "Player";
+
+/// # Sanitize Name
+/// Cleans a name sent by a client, only letters, numbers, spaces, `_` and `-` are kept and it's cut to [`MAX_NAME_LENGTH`].
+pub fn sanitize_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .filter(|character| character.is_alphanumeric() || *character == ' ' || *character == '_' || *character == '-')
+        .take(MAX_NAME_LENGTH)
+        .collect();
+    let cleaned = cleaned.trim();
+
+    if cleaned.is_empty() {
+        DEFAULT_NAME.to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+/// # Unique Name
+/// Adds a number at the end of the name if another player is already using it, for example `Player_2`.
+/// The name is cut to make room for the number, so the result is still a name [`sanitize_name`] keeps as it is.
+///
+/// ## Params:
+/// - name: Name already sanitized.
+/// - used: Names of the other players.
+pub fn unique_name<'a>(name: &str, used: impl Iterator<Item = &'a str> + Clone) -> String {
+    let is_used = |candidate: &str| used.clone().any(|used_name| used_name.eq_ignore_ascii_case(candidate));
+
+    if !is_used(name) {
+        return name.to_string();
+    }
+
+    let mut number = 2;
+    loop {
+        let suffix = format!("_{}", number);
+        let base: String = name.chars().take(MAX_NAME_LENGTH.saturating_sub(suffix.chars().count())).collect();
+        let candidate = format!("{}{}", base.trim_end(), suffix);
+        if !is_used(&candidate) {
+            return candidate;
+        }
+        number += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_removes_invalid_characters_and_cuts_the_name() {
+        assert_eq!(sanitize_name("  Ro(bin)!  "), "Robin");
+        assert_eq!(sanitize_name("a_very-long name with spaces"), "a_very-long name");
+        assert_eq!(sanitize_name("(())"), DEFAULT_NAME);
+    }
+
+    #[test]
+    fn unique_name_keeps_a_free_name() {
+        assert_eq!(unique_name("Robin", ["Ann"].into_iter()), "Robin");
+    }
+
+    #[test]
+    fn unique_name_adds_the_first_free_number() {
+        assert_eq!(unique_name("Robin", ["robin"].into_iter()), "Robin_2");
+        assert_eq!(unique_name("Robin", ["Robin", "Robin_2"].into_iter()), "Robin_3");
+    }
+
+    #[test]
+    fn unique_name_fits_and_survives_sanitize() {
+        let name = "sixteen_chars_xx";
+        let used: Vec<String> = std::iter::once(name.to_string()).chain((2..10).map(|number| format!("sixteen_chars__{}", number))).collect();
+        let unique = unique_name(name, used.iter().map(String::as_str));
+
+        assert_eq!(unique, "sixteen_chars_10");
+        assert!(unique.chars().count() <= MAX_NAME_LENGTH);
+        assert_eq!(sanitize_name(&unique), unique);
+    }
+}
@@ -0,0 +1,41 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::assets::asset_path;
+
+use super::{map::MapData, server_game_logic::Returnable};
+
+/// # Scene
+/// A world state the client can draw without a server, like the one it receives while playing.
+/// The `snapshot` command draws it without a display and saves the frame as a png.
+///
+/// ## File example:
+/// ```json
+/// {
+///     "map": "assets/maps/arena.json",
+///     "local_player": "red",
+///     "snapshot": { "players_data": { ... }, "arrows": [], "pickups": [], "match_state": { ... } }
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Scene {
+    /// Path of the map file, the arena when it's missing.
+    #[serde(default = "Scene::default_map")]
+    pub map: String,
+    /// Id of the player the camera follows, the hud shows its points and arrows.
+    pub local_player: String,
+    pub snapshot: Returnable,
+}
+
+impl Scene {
+    fn default_map() -> String {
+        String::from(MapData::DEFAULT_PATH)
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let path = asset_path(path);
+        let content = fs::read_to_string(&path).map_err(|err| format!("The scene {} couldn't be read: {}", path.display(), err))?;
+        serde_json::from_str(&content).map_err(|err| format!("The scene {} is not valid: {}", path.display(), err))
+    }
+}
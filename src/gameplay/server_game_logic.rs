@@ -1,75 +1,466 @@
 use std::collections::HashMap;
 
+use rand::{seq::SliceRandom, Rng};
 use serde::{Deserialize, Serialize};
 
-use crate::networking::server::Server;
+use crate::{game_object::GameObject, networking::server::Server};
 
-use super::play::Packet;
+use super::{arena::Arena, combat::CombatConfig, map::MapData, match_state::MatchState, movement::MovementConfig, pickup::{Pickup, PickupKind}, team::{Team, TeamConfig}};
 
-#[derive(Serialize, Deserialize, PartialEq)]
+/// Position of an element in the world, it's also used as a plain 2d vector for directions.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
 pub struct Position {
     pub x: f32,
     pub y: f32,
 }
 
-#[derive(Serialize, Deserialize)]
+impl Position {
+    pub fn length(&self) -> f32 {
+        (self.x * self.x + self.y * self.y).sqrt()
+    }
+
+    /// Returns the same direction with a length of 1, a zero vector stays as zero.
+    pub fn normalized(&self) -> Position {
+        let length = self.length();
+
+        if length == 0.0 {
+            return Position { x: 0.0, y: 0.0 };
+        }
+
+        Position { x: self.x / length, y: self.y / length }
+    }
+
+    pub fn scaled(&self, value: f32) -> Position {
+        Position { x: self.x * value, y: self.y * value }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct PlayerData {
+    pub position: Position,
+    /// Pixels per second the player is moving, it's replicated so the client can use it later for prediction.
+    pub velocity: Position,
+    /// Direction the player is looking at, it's where the arrows will be fired to.
+    pub facing: Position,
+    pub health: f32,
+    /// Arrows left on the quiver, firing needs at least one.
+    pub arrows: u32,
+    pub team: Team,
+    /// Display name chosen by the player, the server makes sure no other player is using it.
+    pub name: String,
+    /// Arrows that hit the player since it joined, it's kept when the player respawns so the clients can play the sound of the hit that killed it.
+    #[serde(default)]
+    pub hits: u32,
+}
+
+impl PlayerData {
+    /// The box the player takes on the world, used for collisions.
+    pub fn game_object(&self) -> GameObject {
+        GameObject {
+            active: true,
+            x: self.position.x,
+            y: self.position.y,
+            width: ServerGameLogic::PLAYER_SIZE,
+            height: ServerGameLogic::PLAYER_SIZE,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Arrow {
+    pub owner: String,
+    pub position: Position,
+    pub direction: Position,
+    /// Seconds the arrow has been flying.
+    pub lifetime: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Returnable {
-    pub players_data: HashMap<String, Position>
+    pub players_data: HashMap<String, PlayerData>,
+    pub arrows: Vec<Arrow>,
+    /// Pickups of the map, on the same order as the `pickups` of the [`MapData`].
+    pub pickups: Vec<Pickup>,
+    pub match_state: MatchState,
 }
 
 /// # Server Game Logic
 /// This struct is defined to set all the game logic that will be setted from the server, including stuff like movement, and more based entirely on user input.
-/// This logic is called PER USER, so we can return data like "position, states and more" from the user itself, and based on that 
+/// This logic is called PER USER, so we can return data like "position, states and more" from the user itself, and based on that
 
 pub struct ServerGameLogic {
-    pub returnable: Returnable
+    pub returnable: Returnable,
+    pub map: MapData,
+    pub arena: Arena,
+    pub movement: MovementConfig,
+    pub teams: TeamConfig,
+    pub combat: CombatConfig,
+    // seconds left until each player can fire again
+    fire_cooldowns: HashMap<String, f32>,
 }
 
 impl ServerGameLogic {
-    pub fn new() -> Self {
-        Self {
+    pub const PLAYER_SIZE: f32 = 40.0;
+    pub const MAX_HEALTH: f32 = 100.0;
+    pub const MAX_ARROWS: u32 = 10;
+    pub const PICKUP_POINTS: u32 = 1;
+    pub const PICKUP_HEALTH: f32 = 50.0;
+    pub const PICKUP_ARROWS: u32 = 5;
+    // amount of passes done to separate players, more than one is needed when several players are pushing each other
+    const COLLISION_ITERATIONS: usize = 4;
+
+    pub fn new(map: MapData, movement: MovementConfig, teams: TeamConfig, combat: CombatConfig) -> Self {
+        let mut server_game_logic = Self {
             returnable: Returnable {
-                players_data: HashMap::new()
-            }
-        }
+                players_data: HashMap::new(),
+                arrows: Vec::new(),
+                pickups: Vec::new(),
+                match_state: MatchState::new(),
+            },
+            arena: map.arena(),
+            map,
+            movement,
+            teams,
+            combat,
+            fire_cooldowns: HashMap::new(),
+        };
+
+        server_game_logic.returnable.pickups = server_game_logic.map.pickups.iter().map(|spawn| Pickup {
+            kind: spawn.kind,
+            position: spawn.position.unwrap_or_else(|| server_game_logic.random_free_position(Pickup::SIZE)),
+            active: true,
+            respawn_in: 0.0,
+        }).collect();
+
+        server_game_logic
     }
 
     /// # Update
     /// This function is called once per each connection in the server so we can handle the logic of each connection element.
-    /// 
+    ///
     /// ## Params:
     /// - Connection_id: The client id of the user connected to the server.
-    /// 
+    ///
     /// ## Returns:
     /// - Position of the user to send the client
     pub fn update(&mut self, server: &mut Server) {
-        for (key, data) in &server.connections {
-            let deserialized_data: Result<Packet, serde_json::Error> = serde_json::from_str(&data);
-
-            match deserialized_data {
-                Ok(usable_data) => {
-                    match self.returnable.players_data.get_mut(&usable_data.id) {
-                        Some(existent_player) => {
-                            if usable_data.controls.right {
-                                existent_player.x += 200.0 * server.time.delta_time;
-                            }
-                            if usable_data.controls.left {
-                                existent_player.x -= 200.0 * server.time.delta_time;
-                            }
-                            if usable_data.controls.up {
-                                existent_player.y -= 200.0 * server.time.delta_time;
-                            }
-                            if usable_data.controls.down {
-                                existent_player.y += 200.0 * server.time.delta_time;
-                            }
-                        },
-                        None => {
-                            self.returnable.players_data.insert(usable_data.id, Position { x: 0.0, y: 0.0 });
-                        },
+        let delta_time = server.time.delta_time;
+        self.returnable.match_state.update(delta_time, self.returnable.players_data.len());
+
+        // the players of the clients that left or timed out are removed
+        let connected: Vec<&String> = server.connections.values().map(|connection| &connection.id).collect();
+        let gone: Vec<String> = self.returnable.players_data.keys().filter(|id| !connected.contains(id)).cloned().collect();
+        for id in gone {
+            self.returnable.players_data.remove(&id);
+            self.returnable.match_state.remove_player(&id);
+            self.fire_cooldowns.remove(&id);
+        }
+
+        for connection in server.connections.values_mut() {
+            // the clients on the lobby don't have a player yet
+            if !connection.ready {
+                continue;
+            }
+
+            if let Some(team) = connection.requested_team.take() {
+                self.change_team(&connection.id, team);
+            }
+
+            match self.returnable.players_data.get_mut(&connection.id) {
+                Some(existent_player) => {
+                    let usable_data = match &connection.packet {
+                        Some(packet) => packet,
+                        None => continue,
+                    };
+
+                    let mut direction = Position { x: 0.0, y: 0.0 };
+
+                    if usable_data.controls.right {
+                        direction.x += 1.0;
+                    }
+                    if usable_data.controls.left {
+                        direction.x -= 1.0;
+                    }
+                    if usable_data.controls.up {
+                        direction.y -= 1.0;
+                    }
+                    if usable_data.controls.down {
+                        direction.y += 1.0;
+                    }
+                    // a stick being used wins over the buttons, its length is kept so it can move slower
+                    let stick = usable_data.controls.stick;
+                    if stick.x != 0.0 || stick.y != 0.0 {
+                        direction = if stick.length() > 1.0 { stick.normalized() } else { stick };
+                    }
+
+                    existent_player.velocity = self.movement.apply(existent_player.velocity, direction, delta_time);
+                    existent_player.position.x += existent_player.velocity.x * delta_time;
+                    existent_player.position.y += existent_player.velocity.y * delta_time;
+
+                    // the aim of the mouse wins over the movement, the arrows go where the player looks
+                    match usable_data.controls.aim_direction() {
+                        Some(aim) => existent_player.facing = aim,
+                        None if direction.x != 0.0 || direction.y != 0.0 => existent_player.facing = direction.normalized(),
+                        None => {},
+                    }
+
+                    let cooldown = self.fire_cooldowns.entry(connection.id.clone()).or_insert(0.0);
+                    *cooldown -= delta_time;
+
+                    if usable_data.controls.fire && *cooldown <= 0.0 && existent_player.arrows > 0 && self.returnable.match_state.can_fire() {
+                        *cooldown = self.combat.fire_cooldown;
+                        existent_player.arrows -= 1;
+                        self.returnable.arrows.push(Arrow {
+                            owner: connection.id.clone(),
+                            position: Position {
+                                x: existent_player.position.x + Self::PLAYER_SIZE / 2.0,
+                                y: existent_player.position.y + Self::PLAYER_SIZE / 2.0,
+                            },
+                            direction: existent_player.facing,
+                            lifetime: 0.0,
+                        });
                     }
                 },
-                Err(err) => eprintln!("The value of: {} was not deserialized succesfully, and the reason was: {}", data, err),
+                None => {
+                    let team = self.smallest_team();
+                    let player = self.spawn_player(team, connection.name.clone());
+                    self.returnable.match_state.add_player(&connection.id);
+                    self.returnable.players_data.insert(connection.id.clone(), player);
+                },
             }
         }
+
+        self.resolve_collisions();
+        self.update_arrows(delta_time);
+        self.update_pickups(delta_time);
     }
-}
\ No newline at end of file
+
+    /// Separates the players that are overlapping each other and keeps all of them inside of the arena.
+    fn resolve_collisions(&mut self) {
+        let ids: Vec<String> = self.returnable.players_data.keys().cloned().collect();
+
+        for _ in 0..Self::COLLISION_ITERATIONS {
+            for (index, id) in ids.iter().enumerate() {
+                for other_id in &ids[index + 1..] {
+                    let player = self.returnable.players_data[id].game_object();
+                    let other = self.returnable.players_data[other_id].game_object();
+
+                    if let Some((push_x, push_y)) = player.overlap(&other) {
+                        // each player moves half of the way
+                        if let Some(player) = self.returnable.players_data.get_mut(id) {
+                            player.position.x += push_x / 2.0;
+                            player.position.y += push_y / 2.0;
+                        }
+                        if let Some(other) = self.returnable.players_data.get_mut(other_id) {
+                            other.position.x -= push_x / 2.0;
+                            other.position.y -= push_y / 2.0;
+                        }
+                    }
+                }
+            }
+
+            for player in self.returnable.players_data.values_mut() {
+                let mut object = player.game_object();
+
+                // walls don't move, so the player is pushed all the way out of them
+                for wall in &self.map.walls {
+                    if let Some((push_x, push_y)) = object.overlap(&wall.game_object()) {
+                        object.x += push_x;
+                        object.y += push_y;
+                    }
+                }
+
+                self.arena.clamp(&mut object);
+
+                // hitting a wall or the limits of the arena stops the movement on that axis
+                if object.x != player.position.x {
+                    player.velocity.x = 0.0;
+                }
+                if object.y != player.position.y {
+                    player.velocity.y = 0.0;
+                }
+
+                player.position = Position { x: object.x, y: object.y };
+            }
+        }
+    }
+
+    /// Creates a new player on one of the spawn points of the map, the ones that are not being used by other players are preferred.
+    fn spawn_player(&self, team: Team, name: String) -> PlayerData {
+        let free_spawns: Vec<&Position> = self.map.spawn_points.iter().filter(|spawn| {
+            let spawn_object = GameObject { active: true, x: spawn.x, y: spawn.y, width: Self::PLAYER_SIZE, height: Self::PLAYER_SIZE };
+            self.returnable.players_data.values().all(|player| player.game_object().overlap(&spawn_object).is_none())
+        }).collect();
+
+        let mut rng = rand::thread_rng();
+        let position = match free_spawns.choose(&mut rng) {
+            Some(spawn) => **spawn,
+            None => match self.map.spawn_points.choose(&mut rng) {
+                Some(spawn) => *spawn,
+                None => Position { x: self.arena.x, y: self.arena.y },
+            },
+        };
+
+        PlayerData {
+            position,
+            velocity: Position { x: 0.0, y: 0.0 },
+            facing: Position { x: 1.0, y: 0.0 },
+            health: Self::MAX_HEALTH,
+            arrows: Self::MAX_ARROWS,
+            team,
+            name,
+            hits: 0,
+        }
+    }
+
+    fn team_size(&self, team: Team) -> usize {
+        self.returnable.players_data.values().filter(|player| player.team == team).count()
+    }
+
+    /// The team with less players, new players are added to it so the teams stay balanced.
+    fn smallest_team(&self) -> Team {
+        let mut smallest = Team::ALL[0];
+
+        for team in Team::ALL {
+            if self.team_size(team) < self.team_size(smallest) {
+                smallest = team;
+            }
+        }
+
+        smallest
+    }
+
+    /// Moves a player to the team it asked for, with auto balance it's only done if the new team has less players than the current one.
+    fn change_team(&mut self, id: &str, team: Team) {
+        let (current, name, hits) = match self.returnable.players_data.get(id) {
+            Some(player) => (player.team, player.name.clone(), player.hits),
+            None => return,
+        };
+
+        if current == team || (self.teams.auto_balance && self.team_size(team) >= self.team_size(current)) {
+            return;
+        }
+
+        // changing team counts as a respawn, so the player can't keep the position it had on the other team
+        let player = PlayerData { hits, ..self.spawn_player(team, name) };
+        self.returnable.players_data.insert(id.to_string(), player);
+    }
+
+    /// Moves every arrow and applies the damage of the ones hitting a player, when a player runs out of health the owner of the arrow gets a point and the player respawns.
+    fn update_arrows(&mut self, delta_time: f32) {
+        let players = &mut self.returnable.players_data;
+        let match_state = &mut self.returnable.match_state;
+        let arena = self.arena;
+        let walls = &self.map.walls;
+        let friendly_fire = self.teams.friendly_fire;
+        let combat = self.combat;
+        let mut killed: Vec<String> = Vec::new();
+
+        self.returnable.arrows.retain_mut(|arrow| {
+            arrow.position.x += arrow.direction.x * combat.arrow_speed * delta_time;
+            arrow.position.y += arrow.direction.y * combat.arrow_speed * delta_time;
+            arrow.lifetime += delta_time;
+
+            let owner_team = players.get(&arrow.owner).map(|owner| owner.team);
+
+            for (id, player) in players.iter_mut() {
+                // without friendly fire the arrows go through the players of the same team
+                // a player killed on this tick is only respawned after the arrows, so the next arrows go through it
+                let can_hurt = *id != arrow.owner && player.health > 0.0 && (friendly_fire || owner_team != Some(player.team));
+
+                if can_hurt && player.game_object().contains_point(arrow.position.x, arrow.position.y) {
+                    player.health -= combat.arrow_damage;
+                    player.hits += 1;
+
+                    if player.health <= 0.0 {
+                        match_state.award_points(&arrow.owner, 1);
+                        killed.push(id.clone());
+                    }
+                    return false;
+                }
+            }
+
+            let hit_wall = walls.iter().any(|wall| wall.game_object().contains_point(arrow.position.x, arrow.position.y));
+
+            !hit_wall && arrow.lifetime < combat.arrow_lifetime && arena.contains_point(arrow.position.x, arrow.position.y)
+        });
+
+        // a dead player can't be hit again, this only makes sure nobody respawns twice
+        killed.sort();
+        killed.dedup();
+        for id in killed {
+            if let Some(player) = self.returnable.players_data.get(&id) {
+                let respawned = PlayerData { hits: player.hits, ..self.spawn_player(player.team, player.name.clone()) };
+                self.returnable.players_data.insert(id, respawned);
+            }
+        }
+    }
+
+    /// Gives the pickups to the players touching them and respawns the ones that were collected after their respawn time.
+    fn update_pickups(&mut self, delta_time: f32) {
+        for index in 0..self.returnable.pickups.len() {
+            let pickup = &mut self.returnable.pickups[index];
+
+            if !pickup.active {
+                pickup.respawn_in -= delta_time;
+
+                if pickup.respawn_in <= 0.0 {
+                    let position = match self.map.pickups[index].position {
+                        Some(position) => position,
+                        None => self.random_free_position(Pickup::SIZE),
+                    };
+                    let pickup = &mut self.returnable.pickups[index];
+                    pickup.position = position;
+                    pickup.active = true;
+                }
+                continue;
+            }
+
+            let pickup_object = pickup.game_object();
+            let kind = pickup.kind;
+
+            let collector = self.returnable.players_data.iter_mut().find(|(_, player)| {
+                // a full player doesn't take the pickup so it stays for the others
+                let useful = match kind {
+                    PickupKind::Points => true,
+                    PickupKind::Health => player.health < Self::MAX_HEALTH,
+                    PickupKind::Arrows => player.arrows < Self::MAX_ARROWS,
+                };
+                useful && player.game_object().overlap(&pickup_object).is_some()
+            });
+
+            if let Some((id, player)) = collector {
+                match kind {
+                    PickupKind::Points => self.returnable.match_state.award_points(id, Self::PICKUP_POINTS),
+                    PickupKind::Health => player.health = (player.health + Self::PICKUP_HEALTH).min(Self::MAX_HEALTH),
+                    PickupKind::Arrows => player.arrows = (player.arrows + Self::PICKUP_ARROWS).min(Self::MAX_ARROWS),
+                }
+
+                let pickup = &mut self.returnable.pickups[index];
+                pickup.active = false;
+                pickup.respawn_in = self.map.pickups[index].respawn_time;
+            }
+        }
+    }
+
+    /// A random position inside of the arena where an object of the given size doesn't touch any wall.
+    fn random_free_position(&self, size: f32) -> Position {
+        let mut rng = rand::thread_rng();
+        let mut position = Position { x: self.arena.x, y: self.arena.y };
+
+        // after some tries we give up and use the last one, so a map full of walls can't freeze the server
+        for _ in 0..50 {
+            position = Position {
+                x: rng.gen_range(self.arena.x..=(self.arena.x + self.arena.width - size).max(self.arena.x)),
+                y: rng.gen_range(self.arena.y..=(self.arena.y + self.arena.height - size).max(self.arena.y)),
+            };
+            let object = GameObject { active: true, x: position.x, y: position.y, width: size, height: size };
+
+            if self.map.walls.iter().all(|wall| wall.game_object().overlap(&object).is_none()) {
+                break;
+            }
+        }
+
+        position
+    }
+}
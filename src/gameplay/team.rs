@@ -0,0 +1,60 @@
+use std::fs;
+
+use sdl2::pixels::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::assets::asset_path;
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Team {
+    Red,
+    Blue,
+}
+
+impl Team {
+    pub const ALL: [Team; 2] = [Team::Red, Team::Blue];
+
+    pub fn color(&self) -> Color {
+        match self {
+            Team::Red => Color::RGB(200, 70, 70),
+            Team::Blue => Color::RGB(70, 110, 210),
+        }
+    }
+
+    pub fn other(&self) -> Team {
+        match self {
+            Team::Red => Team::Blue,
+            Team::Blue => Team::Red,
+        }
+    }
+}
+
+/// # Team Config
+/// Rules of the teams loaded by the server from a json file.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct TeamConfig {
+    /// When enabled players can only change to a team that has less players than theirs.
+    pub auto_balance: bool,
+    /// When enabled arrows also hurt the players of the same team as the one that fired them.
+    pub friendly_fire: bool,
+}
+
+impl Default for TeamConfig {
+    fn default() -> Self {
+        Self {
+            auto_balance: true,
+            friendly_fire: false,
+        }
+    }
+}
+
+impl TeamConfig {
+    pub const DEFAULT_PATH: &'static str = "assets/config/teams.json";
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let path = asset_path(path);
+        let content = fs::read_to_string(&path).map_err(|err| format!("The team config {} couldn't be read: {}", path.display(), err))?;
+        serde_json::from_str(&content).map_err(|err| format!("The team config {} is not valid: {}", path.display(), err))
+    }
+}
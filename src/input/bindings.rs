@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use sdl2::{controller::Button, keyboard::Keycode};
+use serde::{Deserialize, Serialize};
+
+/// Things the player can do on a match, each one can be bound to many keys.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Fire,
+    /// Opens the chat, the text is sent with return.
+    Chat,
+    /// Shows the scoreboard while it's held.
+    Scoreboard,
+    /// Saves the frame as a png on the screenshots folder.
+    Screenshot,
+    /// Shows or hides the network stats of the match.
+    NetStats,
+}
+
+impl Action {
+    pub const ALL: [Action; 9] = [Action::MoveUp, Action::MoveDown, Action::MoveLeft, Action::MoveRight, Action::Fire, Action::Chat, Action::Scoreboard, Action::Screenshot, Action::NetStats];
+
+    /// Name shown on the controls screen.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::MoveUp => "Move up",
+            Action::MoveDown => "Move down",
+            Action::MoveLeft => "Move left",
+            Action::MoveRight => "Move right",
+            Action::Fire => "Fire",
+            Action::Chat => "Chat",
+            Action::Scoreboard => "Scoreboard",
+            Action::Screenshot => "Screenshot",
+            Action::NetStats => "Network stats",
+        }
+    }
+}
+
+// the bindings as they are saved, with the names SDL gives to the keys and buttons
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct BindingsFile {
+    keys: HashMap<Action, Vec<String>>,
+    buttons: HashMap<Action, Vec<String>>,
+}
+
+/// # Bindings
+/// Keys and gamepad buttons bound to each [`Action`], they are saved on the client settings with the names SDL gives to them.
+/// The actions that are not on the file keep their default keys and buttons, and a name SDL doesn't know is an error.
+///
+/// ## File example:
+/// ```json
+/// {
+///     "keys": { "MoveUp": ["W", "Up"], "Fire": ["Space"] },
+///     "buttons": { "Fire": ["a", "rightshoulder"] }
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(try_from = "BindingsFile", into = "BindingsFile")]
+pub struct Bindings {
+    keys: HashMap<Action, Vec<Keycode>>,
+    buttons: HashMap<Action, Vec<Button>>,
+}
+
+impl Default for Bindings {
+    // WASD and the arrows move, so both hands can be used
+    fn default() -> Self {
+        let defaults = [
+            (Action::MoveUp, vec![Keycode::W, Keycode::Up]),
+            (Action::MoveDown, vec![Keycode::S, Keycode::Down]),
+            (Action::MoveLeft, vec![Keycode::A, Keycode::Left]),
+            (Action::MoveRight, vec![Keycode::D, Keycode::Right]),
+            (Action::Fire, vec![Keycode::Space]),
+            (Action::Chat, vec![Keycode::Return]),
+            (Action::Scoreboard, vec![Keycode::Tab]),
+            (Action::Screenshot, vec![Keycode::F12]),
+            (Action::NetStats, vec![Keycode::F3]),
+        ];
+
+        // the stick moves too, the d-pad is for the ones that prefer it
+        let buttons = [
+            (Action::MoveUp, vec![Button::DPadUp]),
+            (Action::MoveDown, vec![Button::DPadDown]),
+            (Action::MoveLeft, vec![Button::DPadLeft]),
+            (Action::MoveRight, vec![Button::DPadRight]),
+            (Action::Fire, vec![Button::A, Button::RightShoulder]),
+            (Action::Chat, vec![]),
+            (Action::Scoreboard, vec![Button::Back]),
+            (Action::Screenshot, vec![]),
+            (Action::NetStats, vec![]),
+        ];
+
+        Self { keys: defaults.into_iter().collect(), buttons: buttons.into_iter().collect() }
+    }
+}
+
+impl TryFrom<BindingsFile> for Bindings {
+    type Error = String;
+
+    fn try_from(file: BindingsFile) -> Result<Self, Self::Error> {
+        let mut bindings = Self::default();
+        for (action, names) in file.keys {
+            let mut keys = Vec::new();
+            for name in names {
+                keys.push(Keycode::from_name(&name).ok_or_else(|| format!("the key {} of {} doesn't exist", name, action.name()))?);
+            }
+            keys.truncate(Self::MAX_KEYS);
+            bindings.keys.insert(action, keys);
+        }
+        for (action, names) in file.buttons {
+            let mut buttons = Vec::new();
+            for name in names {
+                buttons.push(Button::from_string(&name).ok_or_else(|| format!("the button {} of {} doesn't exist", name, action.name()))?);
+            }
+            buttons.truncate(Self::MAX_KEYS);
+            bindings.buttons.insert(action, buttons);
+        }
+
+        Ok(bindings)
+    }
+}
+
+impl From<Bindings> for BindingsFile {
+    fn from(bindings: Bindings) -> Self {
+        Self {
+            keys: bindings.keys.iter().map(|(action, keys)| (*action, keys.iter().map(|key| key.name()).collect())).collect(),
+            buttons: bindings.buttons.iter().map(|(action, buttons)| (*action, buttons.iter().map(|button| button.string()).collect())).collect(),
+        }
+    }
+}
+
+impl Bindings {
+    /// Keys (and buttons) an action can have, binding one more replaces the oldest.
+    pub const MAX_KEYS: usize = 3;
+
+    pub fn keys(&self, action: Action) -> &[Keycode] {
+        self.keys.get(&action).map(|keys| keys.as_slice()).unwrap_or(&[])
+    }
+
+    pub fn buttons(&self, action: Action) -> &[Button] {
+        self.buttons.get(&action).map(|buttons| buttons.as_slice()).unwrap_or(&[])
+    }
+
+    /// Every action the key is bound to.
+    pub fn actions(&self, key: Keycode) -> impl Iterator<Item = Action> + '_ {
+        Action::ALL.into_iter().filter(move |action| self.keys(*action).contains(&key))
+    }
+
+    /// Checks if any key of the action is on `held`.
+    pub fn is_held(&self, action: Action, held: &[Keycode]) -> bool {
+        self.keys(action).iter().any(|key| held.contains(key))
+    }
+
+    /// # Bind
+    /// Adds a key to an action, the key is removed from the other actions so one key doesn't do two things.
+    pub fn bind(&mut self, action: Action, key: Keycode) {
+        for keys in self.keys.values_mut() {
+            keys.retain(|bound| *bound != key);
+        }
+
+        let keys = self.keys.entry(action).or_default();
+        keys.push(key);
+        if keys.len() > Self::MAX_KEYS {
+            keys.remove(0);
+        }
+    }
+
+    /// Same as [`Bindings::bind`] with a gamepad button.
+    pub fn bind_button(&mut self, action: Action, button: Button) {
+        for buttons in self.buttons.values_mut() {
+            buttons.retain(|bound| *bound != button);
+        }
+
+        let buttons = self.buttons.entry(action).or_default();
+        buttons.push(button);
+        if buttons.len() > Self::MAX_KEYS {
+            buttons.remove(0);
+        }
+    }
+
+    /// Removes every key and button of the action.
+    pub fn clear(&mut self, action: Action) {
+        self.keys.insert(action, Vec::new());
+        self.buttons.insert(action, Vec::new());
+    }
+
+    /// The keys and buttons of an action as a text, like "W, Up, dpup".
+    pub fn text(&self, action: Action) -> String {
+        let names: Vec<String> = self.keys(action).iter().map(|key| key.name()).chain(self.buttons(action).iter().map(|button| button.string())).collect();
+        if names.is_empty() {
+            return String::from("-");
+        }
+        names.join(", ")
+    }
+}
@@ -1,10 +1,10 @@
 use std::os::raw::c_void;
 
-use sdl2::{mouse::MouseButton, render::{Canvas, Texture, TextureCreator, TextureQuery}, ttf::Font, video::{Window, WindowContext}};
+use sdl2::{mouse::MouseButton, render::{Canvas, TextureQuery}, ttf::Font, video::Window};
 use sdl2::pixels::Color;
 use sdl2::rect::Rect;
 
-use crate::game_object::GameObject;
+use crate::{game_object::GameObject, ui::text_cache::{TextCache, TextQuality}};
 
 #[derive(Clone)]
 pub enum TextAlign {
@@ -27,6 +27,9 @@ pub struct Button {
     pub lclicked: bool,
     pub toggle: Option<bool>,
     pub text_align: TextAlign,
+    pub text_quality: TextQuality,
+    /// Splits the text in lines as wide as the button.
+    pub wrap: bool,
 }
 
 impl Button {
@@ -45,10 +48,12 @@ impl Button {
             lclicked: false,
             toggle,
             text_align,
+            text_quality: TextQuality::Solid,
+            wrap: false,
         }
     }
 
-    pub fn render(&self, canvas: &mut Canvas<Window>, texture_creator: &TextureCreator<WindowContext>, font: &Font) {
+    pub fn render(&self, canvas: &mut Canvas<Window>, text_cache: &mut TextCache, font: &Font) {
         if self.game_object.active == true {
             match self.toggle {
                 Some(value) => {
@@ -64,43 +69,25 @@ impl Button {
             }
             canvas.fill_rect(Rect::new(self.game_object.x as i32, self.game_object.y as i32, self.game_object.width as u32, self.game_object.height as u32)).unwrap();
 
-            // Render the button text
-            match &self.text {
-                Some(_text) => {
-                    match font.render(&_text).solid(self.text_color) {
-                        Ok(surface) => {
-                            match texture_creator.create_texture_from_surface(&surface) {
-                                Ok(texture) => {
-                                    let TextureQuery { width: text_width, height: text_height, .. } = texture.query();
-                                    let mut text_x = 0;
-                                    let mut text_y = 0;
-
-                                    match self.text_align {
-                                        TextAlign::Left => {
-                                            text_x = self.game_object.x as i32;
-                                            text_y = self.game_object.y as i32;
-                                        },
-                                        TextAlign::Center => {
-                                            text_x = self.game_object.x as i32 + (self.game_object.width as i32 - text_width as i32) / 2;
-                                            text_y = self.game_object.y as i32 + (self.game_object.height as i32 - text_height as i32) / 2;
-                                        },
-                                    }
-                        
-                                    // render
-                                    canvas.copy(&texture, None, Rect::new(text_x, text_y, text_width, text_height)).unwrap();
-                                },
-                                // if i do a tabulation this dont works good on calibnration
-                                Err(_) => {},
-                            }
-                        },
-                        Err(_) => {},
+            // Render the button text, the texture is reused until the text changes
+            if let Some(text) = &self.text {
+                let wrap_width = if self.wrap { Some(self.game_object.width as u32) } else { None };
+
+                if let Some(texture) = text_cache.get(font, text, self.text_color, self.text_quality, wrap_width) {
+                    let TextureQuery { width: text_width, height: text_height, .. } = texture.query();
+
+                    let (text_x, text_y) = match self.text_align {
+                        TextAlign::Left => (self.game_object.x as i32, self.game_object.y as i32),
+                        TextAlign::Center => (
+                            self.game_object.x as i32 + (self.game_object.width as i32 - text_width as i32) / 2,
+                            self.game_object.y as i32 + (self.game_object.height as i32 - text_height as i32) / 2,
+                        ),
                     };
-                },
-                None => {
 
-                },
+                    // render
+                    canvas.copy(texture, None, Rect::new(text_x, text_y, text_width, text_height)).unwrap();
+                }
             }
-            
         }
     }
 
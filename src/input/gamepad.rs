@@ -0,0 +1,95 @@
+use sdl2::{controller::{Axis, GameController}, event::Event, GameControllerSubsystem, Sdl};
+
+use crate::{engine::log, gameplay::server_game_logic::Position};
+
+use super::bindings::{Action, Bindings};
+
+/// # Gamepads
+/// Keeps every connected game controller open, they are read directly instead of through button events so every screen sees the same state.
+///
+/// Controllers can be connected and disconnected while the game runs, the screens pass their events to [`Gamepads::handle_event`] so each one is opened or dropped on its own.
+/// SDL also sends a connection event for the controllers that were already connected when the game started.
+pub struct Gamepads {
+    // `None` when SDL couldn't start the game controller subsystem, the game is played with the keyboard
+    subsystem: Option<GameControllerSubsystem>,
+    controllers: Vec<GameController>,
+}
+
+impl Gamepads {
+    /// Part of the stick (from 0 to 1) that is ignored, so a worn stick that doesn't go back to the center doesn't move the player.
+    pub const DEADZONE: f32 = 0.2;
+
+    pub fn new(context: &Sdl) -> Self {
+        let subsystem = match context.game_controller() {
+            Ok(subsystem) => Some(subsystem),
+            Err(err) => {
+                log::warn!("The game controllers weren't initialized, only the keyboard can be used: {}", err);
+                None
+            },
+        };
+
+        Self { subsystem, controllers: Vec::new() }
+    }
+
+    /// Opens the controllers that were connected and drops the ones that were disconnected, the other events are ignored.
+    pub fn handle_event(&mut self, event: &Event) {
+        match event {
+            Event::ControllerDeviceAdded { which, .. } => self.open(*which),
+            Event::ControllerDeviceRemoved { which, .. } => self.close(*which),
+            _ => {},
+        }
+    }
+
+    // the added event has the index of the device, the same controller can be announced twice so it's only kept once
+    fn open(&mut self, index: u32) {
+        let subsystem = match &self.subsystem {
+            Some(subsystem) => subsystem,
+            None => return,
+        };
+
+        match subsystem.open(index) {
+            Ok(controller) => {
+                if self.controllers.iter().any(|open| open.instance_id() == controller.instance_id()) {
+                    return;
+                }
+                log::info!("Gamepad connected: {}", controller.name());
+                self.controllers.push(controller);
+            },
+            Err(err) => log::warn!("The gamepad {} couldn't be opened: {}", index, err),
+        }
+    }
+
+    // the removed event has the instance id of the controller, not its index
+    fn close(&mut self, instance_id: u32) {
+        if let Some(position) = self.controllers.iter().position(|controller| controller.instance_id() == instance_id) {
+            let controller = self.controllers.remove(position);
+            log::info!("Gamepad disconnected: {}", controller.name());
+        }
+    }
+
+    /// # Stick
+    /// Position of the left stick of the first controller that is moving it, from -1 to 1 on each axis.
+    ///
+    /// ## Returns:
+    /// - A zero vector inside the deadzone, outside it the length goes from 0 to 1 so small movements are still possible.
+    pub fn stick(&self) -> Position {
+        for controller in &self.controllers {
+            let stick = Position {
+                x: controller.axis(Axis::LeftX) as f32 / i16::MAX as f32,
+                y: controller.axis(Axis::LeftY) as f32 / i16::MAX as f32,
+            };
+
+            let length = stick.length().min(1.0);
+            if length > Self::DEADZONE {
+                return stick.normalized().scaled((length - Self::DEADZONE) / (1.0 - Self::DEADZONE));
+            }
+        }
+
+        Position { x: 0.0, y: 0.0 }
+    }
+
+    /// Checks if any controller is holding a button of the action.
+    pub fn is_held(&self, action: Action, bindings: &Bindings) -> bool {
+        self.controllers.iter().any(|controller| bindings.buttons(action).iter().any(|button| controller.button(*button)))
+    }
+}
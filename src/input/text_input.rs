@@ -0,0 +1,264 @@
+use std::time::Instant;
+
+use sdl2::{clipboard::ClipboardUtil, event::Event, keyboard::{Keycode, Mod}, mouse::MouseButton, pixels::Color, rect::Rect, render::{Canvas, TextureQuery}, ttf::Font, video::Window};
+
+use crate::{game_object::GameObject, ui::text_cache::{TextCache, TextQuality}};
+
+/// Function that checks the whole text of a [`TextInput`].
+pub type Validator = fn(&str) -> Result<(), String>;
+
+/// # Text Input
+/// A box where the player can write text, it uses the text input events of SDL so any keyboard layout works.
+///
+/// It supports a cursor, selection with shift (or ctrl + a), backspace/delete, copy and paste with the clipboard and focus by clicking on it.
+pub struct TextInput {
+    pub game_object: GameObject,
+    pub text: String,
+    /// Text shown in gray while the input is empty.
+    pub placeholder: String,
+    pub focused: bool,
+    pub max_length: usize,
+    /// Decides which characters can be written, by default every character is allowed.
+    pub allowed: Option<fn(char) -> bool>,
+    /// Checks the whole text, the error is used to show why the text can't be used.
+    pub validate: Option<Validator>,
+    pub color: Color,
+    pub text_color: Color,
+    pub focus_color: Color,
+    // the cursor and the selection are counted in characters, not in bytes
+    cursor: usize,
+    selection_anchor: Option<usize>,
+    // the cursor blinks from the last time something changed
+    last_change: Instant,
+}
+
+impl TextInput {
+    const PADDING: i32 = 6;
+
+    pub fn new(game_object: GameObject, text: &str, placeholder: &str, max_length: usize) -> Self {
+        Self {
+            game_object,
+            text: text.to_string(),
+            placeholder: placeholder.to_string(),
+            focused: false,
+            max_length,
+            allowed: None,
+            validate: None,
+            color: Color::RGB(30, 30, 35),
+            text_color: Color::WHITE,
+            focus_color: Color::RGB(100, 100, 130),
+            cursor: text.chars().count(),
+            selection_anchor: None,
+            last_change: Instant::now(),
+        }
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.chars().take(self.max_length).collect();
+        self.cursor = self.text.chars().count();
+        self.selection_anchor = None;
+        self.last_change = Instant::now();
+    }
+
+    pub fn focus(&mut self) {
+        self.focused = true;
+        self.cursor = self.text.chars().count();
+        self.selection_anchor = None;
+        self.last_change = Instant::now();
+    }
+
+    pub fn unfocus(&mut self) {
+        self.focused = false;
+        self.selection_anchor = None;
+    }
+
+    /// The result of the `validate` hook, without a hook every text is valid.
+    pub fn validation(&self) -> Result<(), String> {
+        match self.validate {
+            Some(validate) => validate(&self.text),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the start and end (in characters) of the selected text.
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        match self.selection_anchor {
+            Some(anchor) if anchor != self.cursor => Some((anchor.min(self.cursor), anchor.max(self.cursor))),
+            _ => None,
+        }
+    }
+
+    /// # Handle Event
+    /// Updates the input with an SDL event, clicks change the focus and the keyboard is only used while it's focused.
+    ///
+    /// ## Returns:
+    /// - `true` if the event was used by the input, so it shouldn't be used by anything else.
+    pub fn handle_event(&mut self, event: &Event, clipboard: &ClipboardUtil) -> bool {
+        match event {
+            Event::MouseButtonDown { mouse_btn: MouseButton::Left, x, y, .. } => {
+                let inside = self.game_object.active && self.game_object.contains_point(*x as f32, *y as f32);
+                if inside && !self.focused {
+                    self.focus();
+                } else if !inside {
+                    self.unfocus();
+                }
+                inside
+            },
+            Event::TextInput { text, .. } if self.focused => {
+                self.insert(text);
+                true
+            },
+            Event::KeyDown { keycode: Some(key), keymod, .. } if self.focused => {
+                let shift = keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD);
+                let ctrl = keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD | Mod::LGUIMOD | Mod::RGUIMOD);
+                let length = self.text.chars().count();
+
+                match *key {
+                    Keycode::Backspace => {
+                        if !self.delete_selection() && self.cursor > 0 {
+                            self.remove_range(self.cursor - 1, self.cursor);
+                        }
+                    },
+                    Keycode::Delete => {
+                        if !self.delete_selection() && self.cursor < length {
+                            self.remove_range(self.cursor, self.cursor + 1);
+                        }
+                    },
+                    Keycode::Left => self.move_cursor(self.cursor.saturating_sub(1), shift),
+                    Keycode::Right => self.move_cursor((self.cursor + 1).min(length), shift),
+                    Keycode::Home => self.move_cursor(0, shift),
+                    Keycode::End => self.move_cursor(length, shift),
+                    Keycode::A if ctrl => {
+                        self.selection_anchor = Some(0);
+                        self.cursor = length;
+                    },
+                    Keycode::C if ctrl => {
+                        if let Some((start, end)) = self.selection() {
+                            let _ = clipboard.set_clipboard_text(&self.slice(start, end));
+                        }
+                    },
+                    Keycode::X if ctrl => {
+                        if let Some((start, end)) = self.selection() {
+                            let _ = clipboard.set_clipboard_text(&self.slice(start, end));
+                            self.delete_selection();
+                        }
+                    },
+                    Keycode::V if ctrl => {
+                        if let Ok(pasted) = clipboard.clipboard_text() {
+                            // only the first line is used, the inputs have a single line
+                            let line = pasted.lines().next().unwrap_or("").to_string();
+                            self.insert(&line);
+                        }
+                    },
+                    _ => return false,
+                }
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Writes text on the cursor position, replacing the selection if there is one.
+    pub fn insert(&mut self, text: &str) {
+        self.delete_selection();
+
+        let allowed: String = text.chars().filter(|character| !character.is_control() && self.allowed.is_none_or(|allowed| allowed(*character))).collect();
+        let space_left = self.max_length.saturating_sub(self.text.chars().count());
+        let allowed: String = allowed.chars().take(space_left).collect();
+
+        let byte_index = self.byte_index(self.cursor);
+        self.text.insert_str(byte_index, &allowed);
+        self.cursor += allowed.chars().count();
+        self.last_change = Instant::now();
+    }
+
+    pub fn render(&self, canvas: &mut Canvas<Window>, text_cache: &mut TextCache, font: &Font) {
+        if !self.game_object.active {
+            return;
+        }
+
+        let rect = Rect::new(self.game_object.x as i32, self.game_object.y as i32, self.game_object.width as u32, self.game_object.height as u32);
+        canvas.set_draw_color(self.color);
+        canvas.fill_rect(rect).unwrap();
+        canvas.set_draw_color(if self.focused { self.focus_color } else { Color::RGB(70, 70, 80) });
+        canvas.draw_rect(rect).unwrap();
+
+        let text_x = rect.x() + Self::PADDING;
+        let line_height = font.height();
+        let text_y = rect.y() + (rect.height() as i32 - line_height) / 2;
+
+        // the selection goes under the text
+        if let Some((start, end)) = self.selection() {
+            let start_x = text_x + self.text_width(font, start);
+            let end_x = text_x + self.text_width(font, end);
+            canvas.set_draw_color(Color::RGBA(90, 120, 200, 160));
+            canvas.fill_rect(Rect::new(start_x, text_y, (end_x - start_x).max(1) as u32, line_height as u32)).unwrap();
+        }
+
+        let (shown, color) = if self.text.is_empty() { (&self.placeholder, Color::GRAY) } else { (&self.text, self.text_color) };
+        if let Some(texture) = text_cache.get(font, shown, color, TextQuality::Blended, None) {
+            let TextureQuery { width, height, .. } = texture.query();
+            // the text is cut so it doesn't go out of the box
+            let visible_width = width.min(rect.width().saturating_sub(2 * Self::PADDING as u32));
+            canvas.copy(texture, Rect::new(0, 0, visible_width, height), Rect::new(text_x, text_y, visible_width, height)).unwrap();
+        }
+
+        // the cursor blinks every half second
+        if self.focused && self.last_change.elapsed().as_millis() % 1000 < 500 {
+            let cursor_x = text_x + self.text_width(font, self.cursor);
+            canvas.set_draw_color(self.text_color);
+            canvas.fill_rect(Rect::new(cursor_x, text_y, 2, line_height as u32)).unwrap();
+        }
+    }
+
+    fn move_cursor(&mut self, position: usize, select: bool) {
+        if select {
+            if self.selection_anchor.is_none() {
+                self.selection_anchor = Some(self.cursor);
+            }
+        } else {
+            self.selection_anchor = None;
+        }
+
+        self.cursor = position;
+        self.last_change = Instant::now();
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        match self.selection() {
+            Some((start, end)) => {
+                self.remove_range(start, end);
+                true
+            },
+            None => {
+                self.selection_anchor = None;
+                false
+            },
+        }
+    }
+
+    fn remove_range(&mut self, start: usize, end: usize) {
+        let start_byte = self.byte_index(start);
+        let end_byte = self.byte_index(end);
+        self.text.replace_range(start_byte..end_byte, "");
+        self.cursor = start;
+        self.selection_anchor = None;
+        self.last_change = Instant::now();
+    }
+
+    fn slice(&self, start: usize, end: usize) -> String {
+        self.text.chars().skip(start).take(end - start).collect()
+    }
+
+    fn byte_index(&self, characters: usize) -> usize {
+        self.text.char_indices().nth(characters).map_or(self.text.len(), |(index, _)| index)
+    }
+
+    fn text_width(&self, font: &Font, characters: usize) -> i32 {
+        let text = &self.text[..self.byte_index(characters)];
+        if text.is_empty() {
+            return 0;
+        }
+        font.size_of(text).map_or(0, |(width, _)| width as i32)
+    }
+}
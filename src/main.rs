@@ -1,68 +1,102 @@
 use app::App;
+use engine::{log, screenshot};
+use gameplay::scene::Scene;
 use networking::server::Server;
-use std::collections::HashMap;
-use std::io::ErrorKind;
-use std::net::{SocketAddr, UdpSocket};
-use std::{io, string, thread, time};
+use std::{env, path::Path};
 
 mod app;
 mod game_object;
 
 mod ui {
+    pub mod chat;
+    pub mod layout;
+    pub mod manager;
+    pub mod net_overlay;
     pub mod text;
+    pub mod text_cache;
 }
 
 mod engine {
+    pub mod assets;
+    pub mod audio;
+    pub mod camera;
+    pub mod display;
+    pub mod log;
+    pub mod screenshot;
+    pub mod settings;
+    pub mod sprite;
     pub mod time;
 }
 
 mod input {
+    pub mod bindings;
     pub mod button_module;
+    pub mod gamepad;
+    pub mod text_input;
 }
 
 mod gameplay {
+    pub mod arena;
+    pub mod combat;
+    pub mod lobby;
+    pub mod map;
+    pub mod match_state;
+    pub mod menu;
+    pub mod movement;
+    pub mod pickup;
     pub mod play;
+    pub mod player_name;
+    pub mod scene;
     pub mod server_game_logic;
+    pub mod team;
 }
 
 mod networking {
+    pub mod error;
+    pub mod protocol;
+    pub mod reliable;
     pub mod server;
+    pub mod stats;
 }
 
 fn main() -> Result<(), String> {
-    print!("{}[2J", 27 as char); // clean the terminal
+    let args: Vec<String> = env::args().skip(1).collect();
 
-    println!("You want to do a [server] or a [client]");
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).expect("Failed to read line");
-        
-    if input.trim() == "server"{
-        let mut server = Server::new();
-        server.run();
-    } else if input.trim() == "client" {
-        println!("Enter the IP to connect");
-        let mut ip = String::new();
+    // `cargo run -- server` starts a dedicated server on the terminal, without arguments the game window is opened
+    match args.first().map(|arg| arg.as_str()) {
+        Some("server") => {
+            print!("{}[2J", 27 as char); // clean the terminal
 
-        match io::stdin().read_line(&mut ip) {
-            Ok(_) => {
-                if ip.trim() != "" {
-                    let socket = UdpSocket::bind("0.0.0.0:0").expect("Failed to create socket");
-                    socket.set_nonblocking(true).expect("Failed to set non-blocking mode");
-        
-                    let local_addr = socket.local_addr().expect("Failed to get local address");
-                    println!("Client started at:\n - ip: {}:{}", local_addr.ip(), local_addr.port());
-                
-                    socket.send_to("Connected".as_bytes(), ip.trim().to_owned()).expect("Failed to send data");
-                    
-                    let app = App::new("Multiplayer Testing", socket, ip.trim().to_owned());
-                    app.render();
-                }
-            },
-            Err(error) => eprintln!("Something went wrong: {}", error),
-        }
-
-        
+            let mut server = Server::new();
+            server.run();
+        },
+        Some("snapshot") => snapshot(&args[1..])?,
+        _ => {
+            let app = App::new("Multiplayer Testing");
+            app.render();
+        },
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// `cargo run -- snapshot assets/scenes/duel.json duel.png 1280 720` draws a scene without a display and saves it as a png
+fn snapshot(args: &[String]) -> Result<(), String> {
+    let usage = "Usage: snapshot <scene.json> <output.png> [width] [height]";
+    let (scene, output) = match args {
+        [scene, output, ..] => (scene, output),
+        _ => return Err(String::from(usage)),
+    };
+    let size = |index: usize, default: u32| match args.get(index) {
+        Some(value) => value.parse::<u32>().map_err(|_| format!("The size {} is not a number. {}", value, usage)),
+        None => Ok(default),
+    };
+    let (width, height) = (size(2, 1280)?, size(3, 720)?);
+
+    let scene = Scene::load(scene)?;
+    let frame = App::headless(width, height).render_scene(&scene)?;
+    screenshot::save_png(&frame, Path::new(output))?;
+
+    log::info!("The scene was saved on {}", output);
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use std::{error::Error, fmt, io, net::{SocketAddr, ToSocketAddrs, UdpSocket}, str::Utf8Error};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Biggest payload an UDP datagram can have over IPv4, a message longer than this can't be sent at all.
+pub const MAX_DATAGRAM: usize = 65_507;
+
+/// # Network Error
+/// Everything that can go wrong while sending or receiving a message, the client and the server decide what to do with each one instead of stopping.
+#[derive(Debug)]
+pub enum NetworkError {
+    /// The socket couldn't send a datagram to the address, the peer is usually gone.
+    Send { address: String, source: io::Error },
+    /// The socket failed while receiving, it doesn't say which peer caused it.
+    Receive(io::Error),
+    /// A datagram that is not text, so it can't be a message of the protocol.
+    InvalidUtf8 { from: SocketAddr, source: Utf8Error },
+    /// A datagram that is text but not a message of the protocol (another game, an old version or someone testing the server).
+    InvalidMessage { from: SocketAddr, source: serde_json::Error },
+    /// A message of ours couldn't be written as json.
+    Serialize(serde_json::Error),
+    /// A message of ours is longer than [`MAX_DATAGRAM`], it's not sent to anyone.
+    TooLarge { size: usize },
+}
+
+impl NetworkError {
+    /// The peer that sent the datagram, `None` for the errors that don't come from a datagram.
+    pub fn peer(&self) -> Option<SocketAddr> {
+        match self {
+            NetworkError::InvalidUtf8 { from, .. } | NetworkError::InvalidMessage { from, .. } => Some(*from),
+            _ => None,
+        }
+    }
+
+    /// # Is Peer Error
+    /// If a failed send is about the peer it was sent to (like an ICMP unreachable that came back), and not about the socket.
+    /// A full send buffer or an interrupted call are not the fault of the peer, so they are not counted against it.
+    pub fn is_peer_error(&self) -> bool {
+        match self {
+            NetworkError::Send { source, .. } => matches!(
+                source.kind(),
+                io::ErrorKind::ConnectionRefused | io::ErrorKind::ConnectionReset | io::ErrorKind::HostUnreachable | io::ErrorKind::NetworkUnreachable | io::ErrorKind::AddrNotAvailable
+            ),
+            _ => false,
+        }
+    }
+
+    /// # Is Connection Reset
+    /// On Windows a datagram sent to a closed port makes the next receive fail with a reset (from the ICMP port unreachable that came back).
+    /// It's about a peer that left, the socket is still fine.
+    pub fn is_connection_reset(&self) -> bool {
+        match self {
+            NetworkError::Receive(err) => matches!(err.kind(), io::ErrorKind::ConnectionReset | io::ErrorKind::ConnectionRefused),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkError::Send { address, source } => write!(f, "The data couldn't be sent to {}: {}", address, source),
+            NetworkError::Receive(source) => write!(f, "The data couldn't be received: {}", source),
+            NetworkError::InvalidUtf8 { from, source } => write!(f, "The data from {} is not valid text: {}", from, source),
+            NetworkError::InvalidMessage { from, source } => write!(f, "The data from {} is not a valid message: {}", from, source),
+            NetworkError::Serialize(source) => write!(f, "The message couldn't be serialized: {}", source),
+            NetworkError::TooLarge { size } => write!(f, "The message has {} bytes, more than the {} that fit on a datagram", size, MAX_DATAGRAM),
+        }
+    }
+}
+
+impl Error for NetworkError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            NetworkError::Send { source, .. } | NetworkError::Receive(source) => Some(source),
+            NetworkError::InvalidUtf8 { source, .. } => Some(source),
+            NetworkError::InvalidMessage { source, .. } | NetworkError::Serialize(source) => Some(source),
+            NetworkError::TooLarge { .. } => None,
+        }
+    }
+}
+
+/// Writes a message as the json that is sent on each datagram, it fails if it doesn't fit on one.
+pub fn encode<T: Serialize>(message: &T) -> Result<String, NetworkError> {
+    let data = serde_json::to_string(message).map_err(NetworkError::Serialize)?;
+    if data.len() > MAX_DATAGRAM {
+        return Err(NetworkError::TooLarge { size: data.len() });
+    }
+    Ok(data)
+}
+
+/// # Decode
+/// Reads a message from a received datagram.
+///
+/// ## Params:
+/// - datagram: The bytes received, only the part that was filled by the socket.
+/// - from: Address that sent it, it's kept on the error so the bad peer can be found.
+pub fn decode<T: DeserializeOwned>(datagram: &[u8], from: SocketAddr) -> Result<T, NetworkError> {
+    let text = std::str::from_utf8(datagram).map_err(|source| NetworkError::InvalidUtf8 { from, source })?;
+    serde_json::from_str(text).map_err(|source| NetworkError::InvalidMessage { from, source })
+}
+
+/// # Send To
+/// Sends a message as one datagram.
+///
+/// ## Returns:
+/// - The bytes that were sent, for the network stats.
+pub fn send_to<T: Serialize, A: ToSocketAddrs + fmt::Display>(socket: &UdpSocket, message: &T, address: A) -> Result<usize, NetworkError> {
+    let data = encode(message)?;
+    socket.send_to(data.as_bytes(), &address).map_err(|source| NetworkError::Send { address: address.to_string(), source })
+}
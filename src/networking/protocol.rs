@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+use crate::gameplay::{map::MapData, play::Packet, server_game_logic::Returnable, team::Team};
+
+/// # Client Message
+/// Every datagram sent from a client to the server is one of these values serialized as json.
+#[derive(Serialize, Deserialize)]
+pub enum ClientMessage {
+    /// Sent when connecting, the client keeps sending it until it gets a `ServerMessage::Welcome`.
+    Join { name: String },
+    /// State of the controls of the player.
+    Input(Packet),
+    /// Asks the server to move the player to another team.
+    ChooseTeam(Team),
+    /// Chat text written by the player, it's sent with a [`ReliableSender`](super::reliable::ReliableSender) until the server acknowledges it.
+    Chat { sequence: u32, text: String },
+    /// Acknowledges a `ServerMessage::Chat`.
+    ChatAck { sequence: u32 },
+    /// The player left the lobby, so the server can spawn it.
+    Ready,
+    /// Sent when nothing else was sent for a while, so the server doesn't drop the client.
+    KeepAlive,
+    /// Asks for a `ServerMessage::Pong` to measure the round trip time, the sequence tells which ping is answered.
+    Ping { sequence: u32 },
+    /// The player left the game.
+    Leave,
+}
+
+/// # Server Message
+/// Every datagram sent from the server to a client is one of these values serialized as json.
+#[derive(Serialize, Deserialize)]
+pub enum ServerMessage {
+    /// Answer to a `ClientMessage::Join`, it has the map the server is using, the name the player got after validating it and the id of its player on the snapshots.
+    Welcome { map: MapData, name: String, id: String },
+    /// State of the game on the current server tick.
+    Snapshot(Returnable),
+    /// Chat message broadcasted to every player, it's sent again until the client acknowledges it.
+    Chat { sequence: u32, message: ChatMessage },
+    /// Acknowledges a `ClientMessage::Chat`.
+    ChatAck { sequence: u32 },
+    /// Answer to a `ClientMessage::Join` that can't be accepted, for example when the server is full.
+    Rejected { reason: String },
+    /// Answer to a `ClientMessage::Ping`, with the current tick and the inputs of the client the server got on its last tick.
+    Pong { sequence: u32, tick: u64, input_depth: u32 },
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ChatMessage {
+    /// Name of the player that wrote the message, `None` for messages of the server itself.
+    pub from: Option<String>,
+    pub text: String,
+}
+
+impl ChatMessage {
+    pub const MAX_LENGTH: usize = 120;
+
+    /// Removes the control characters of a chat text and cuts it to [`ChatMessage::MAX_LENGTH`], it returns `None` if nothing is left.
+    pub fn sanitize(text: &str) -> Option<String> {
+        let cleaned: String = text.chars().filter(|character| !character.is_control()).take(Self::MAX_LENGTH).collect();
+        let cleaned = cleaned.trim();
+
+        if cleaned.is_empty() {
+            None
+        } else {
+            Some(cleaned.to_string())
+        }
+    }
+}
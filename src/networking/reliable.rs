@@ -0,0 +1,107 @@
+use std::{collections::BTreeMap, time::{Duration, Instant}};
+
+struct PendingMessage<T> {
+    sequence: u32,
+    message: T,
+    last_sent: Instant,
+}
+
+/// # Reliable Sender
+/// Keeps the messages that have to arrive no matter what (like chat), every message gets a sequence number and it's sent again until the other side acknowledges it.
+pub struct ReliableSender<T> {
+    next_sequence: u32,
+    pending: Vec<PendingMessage<T>>,
+}
+
+impl<T: Clone> ReliableSender<T> {
+    /// Time waited for an acknowledge before sending a message again.
+    pub const RESEND_TIME: Duration = Duration::from_millis(250);
+    /// Messages that can wait for an acknowledge, it's the same as the window of the receiver since it doesn't keep more than that.
+    pub const MAX_PENDING: usize = ReliableReceiver::<T>::WINDOW as usize;
+
+    pub fn new() -> Self {
+        Self {
+            next_sequence: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// # Send
+    /// Registers a new message, the returned sequence has to be sent together with it.
+    ///
+    /// ## Returns:
+    /// - `None` if [`ReliableSender::MAX_PENDING`] messages are still waiting, the other side stopped acknowledging them so the message is not kept.
+    pub fn send(&mut self, message: T) -> Option<u32> {
+        if self.pending.len() >= Self::MAX_PENDING {
+            return None;
+        }
+
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        self.pending.push(PendingMessage { sequence, message, last_sent: Instant::now() });
+        Some(sequence)
+    }
+
+    /// The other side got the message, so it doesn't have to be sent again.
+    pub fn acknowledge(&mut self, sequence: u32) {
+        self.pending.retain(|pending| pending.sequence != sequence);
+    }
+
+    /// Returns the messages that were not acknowledged on time, they are marked as sent again.
+    pub fn due_for_resend(&mut self) -> Vec<(u32, T)> {
+        let mut resend = Vec::new();
+
+        for pending in &mut self.pending {
+            if pending.last_sent.elapsed() >= Self::RESEND_TIME {
+                pending.last_sent = Instant::now();
+                resend.push((pending.sequence, pending.message.clone()));
+            }
+        }
+
+        resend
+    }
+}
+
+/// # Reliable Receiver
+/// The other side of a [`ReliableSender`], it drops the duplicated messages and gives them back in the same order they were sent.
+///
+/// Only the messages inside [`ReliableReceiver::WINDOW`] are kept while an older one is missing, so the other side can't fill the memory with sequences far ahead.
+pub struct ReliableReceiver<T> {
+    next_expected: u32,
+    out_of_order: BTreeMap<u32, T>,
+}
+
+impl<T> ReliableReceiver<T> {
+    /// Sequences after the next expected one that are kept, it's also the most messages that can wait for an older one.
+    pub const WINDOW: u32 = 64;
+
+    pub fn new() -> Self {
+        Self {
+            next_expected: 0,
+            out_of_order: BTreeMap::new(),
+        }
+    }
+
+    /// # Receive
+    /// Saves a received message, it has to be acknowledged even if it's a duplicate, since the acknowledge of the first one could have been lost.
+    ///
+    /// ## Returns:
+    /// - `None` if the message is too far ahead of the window, it's dropped without acknowledge so the sender tries again later.
+    /// - The messages that are ready to be used, in order. It's empty if the message was a duplicate or if an older one is still missing.
+    pub fn receive(&mut self, sequence: u32, message: T) -> Option<Vec<T>> {
+        if sequence >= self.next_expected {
+            if sequence - self.next_expected >= Self::WINDOW {
+                return None;
+            }
+            self.out_of_order.entry(sequence).or_insert(message);
+        }
+
+        let mut ready = Vec::new();
+        while let Some(message) = self.out_of_order.remove(&self.next_expected) {
+            ready.push(message);
+            self.next_expected += 1;
+        }
+
+        Some(ready)
+    }
+}
@@ -1,104 +1,539 @@
-use std::{collections::HashMap, io::ErrorKind, net::{SocketAddr, UdpSocket}, time::Instant};
+use std::{collections::{HashMap, VecDeque}, io::ErrorKind, net::{SocketAddr, UdpSocket}, sync::{atomic::{AtomicBool, Ordering}, Arc}, thread::{self, JoinHandle}, time::{Duration, Instant}};
 
-use crate::{engine::time::Timing, gameplay::server_game_logic::ServerGameLogic};
+use crate::{engine::{log, settings::ServerSettings, time::Timing}, gameplay::{map::MapData, movement::MovementConfig, play::Packet, player_name, server_game_logic::ServerGameLogic, team::{Team, TeamConfig}}, networking::{error::{self, NetworkError}, protocol::{ChatMessage, ClientMessage, ServerMessage}, reliable::{ReliableReceiver, ReliableSender}}};
 
+/// # Connection
+/// Data the server keeps for each one of the connected clients.
+pub struct Connection {
+    /// Id the server gave to the client when it joined, it's the key of the player on the game logic.
+    /// It's never taken from the client, so a client can't use the player of another one.
+    pub id: String,
+    /// Display name of the player, already validated and unique on the server.
+    pub name: String,
+    /// Last controls received from the client, it's `None` until the first input arrives.
+    pub packet: Option<Packet>,
+    /// Team the client asked to change to, the game logic takes it on the next tick.
+    pub requested_team: Option<Team>,
+    /// Chat texts of the client, they are cleaned before waiting on the receiver so a long text doesn't stay on memory.
+    pub chat_in: ReliableReceiver<Option<String>>,
+    pub chat_out: ReliableSender<ChatMessage>,
+    /// The player leaves the lobby and gets spawned once the client sends `ClientMessage::Ready`.
+    pub ready: bool,
+    /// Used to drop the clients that stopped sending data without saying goodbye.
+    pub last_message: Instant,
+    /// Inputs received since the last tick, only the last one is used.
+    pub inputs: u32,
+    /// Inputs that were received for the last tick, it's sent on each `ServerMessage::Pong` for the debug overlay.
+    pub input_depth: u32,
+    /// Datagrams from this client that were not valid messages, after [`Server::MAX_INVALID_MESSAGES`] it's kicked.
+    pub invalid_messages: u32,
+    /// Sends to this client that failed in a row because of it, after [`Server::MAX_SEND_FAILURES`] it's kicked.
+    pub send_failures: u32,
+    /// When the last chat messages of the client were accepted, only [`Server::CHAT_LIMIT`] fit on [`Server::CHAT_WINDOW`].
+    pub chat_times: VecDeque<Instant>,
+}
+
+/// # Hosted Server
+/// A server running on a thread of the game itself, it's stopped when this value is dropped.
+pub struct HostedServer {
+    pub address: SocketAddr,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for HostedServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
 
 /// # Server
 /// Server is a struct that will handle the creation and data obtaining from the clients, general elements of the server like timing, and more data.
 pub struct Server {
     pub time: Timing,
-    pub connections: HashMap<String, String>,
+    pub settings: ServerSettings,
+    pub map: MapData,
+    pub movement: MovementConfig,
+    pub teams: TeamConfig,
+    pub connections: HashMap<String, Connection>,
     pub last_data_sent: String,
+    last_data_sent_at: Instant,
+    /// Ticks since the server started.
+    pub tick: u64,
+    // the id of the next client that joins, each one gets a new one
+    next_player_id: u64,
+    running: Arc<AtomicBool>,
 }
 
 impl Server {
-    const TICK_RATE: f32 = 60.0;
+    // the snapshot is sent at least this often even if nothing changed, so clients know the server is still there
+    const KEEP_ALIVE: Duration = Duration::from_secs(1);
+    // clients that don't send anything for this long are dropped
+    const TIMEOUT: Duration = Duration::from_secs(10);
+    // ticks that take too long are counted and written at most once each this time, so a slow machine doesn't flood the log
+    const OVERRUN_REPORT: Duration = Duration::from_secs(1);
+    /// Invalid datagrams a connected client can send before it's kicked, a few are allowed since a newer client could send something this server doesn't know.
+    pub const MAX_INVALID_MESSAGES: u32 = 5;
+    /// Sends in a row that can fail because of a client before it's kicked, a single ICMP unreachable can come from a client that is still there.
+    pub const MAX_SEND_FAILURES: u32 = 10;
+    /// Chat messages a client can send on [`Server::CHAT_WINDOW`], the rest are acknowledged but not shown to anyone.
+    pub const CHAT_LIMIT: usize = 5;
+    pub const CHAT_WINDOW: Duration = Duration::from_secs(5);
 
     pub fn new() -> Self {
+        let (settings, error) = match ServerSettings::load(ServerSettings::DEFAULT_PATH) {
+            Ok(settings) => (settings, None),
+            Err(err) => (ServerSettings::default(), Some(err)),
+        };
+
+        // when the server is hosted from the menu the game already started the log, and it's kept
+        log::init(&settings.log);
+        if let Some(err) = error {
+            log::warn!("{}, using the default server settings instead", err);
+        }
+
+        let map = match MapData::load(&settings.map) {
+            Ok(map) => map,
+            Err(err) => {
+                log::warn!("{}, using an empty map instead", err);
+                MapData::default()
+            },
+        };
+
+        let movement = match MovementConfig::load(MovementConfig::DEFAULT_PATH) {
+            Ok(movement) => movement,
+            Err(err) => {
+                log::warn!("{}, using the default movement instead", err);
+                MovementConfig::default()
+            },
+        };
+
+        let teams = match TeamConfig::load(TeamConfig::DEFAULT_PATH) {
+            Ok(teams) => teams,
+            Err(err) => {
+                log::warn!("{}, using the default team rules instead", err);
+                TeamConfig::default()
+            },
+        };
+
         Self {
             connections: HashMap::new(),
             time: Timing::new(),
-             last_data_sent: "".to_string()
+            settings,
+            map,
+            movement,
+            teams,
+            last_data_sent: "".to_string(),
+            last_data_sent_at: Instant::now(),
+            tick: 0,
+            next_player_id: 0,
+            running: Arc::new(AtomicBool::new(true)),
         }
     }
 
     pub fn run(&mut self) {
-        match UdpSocket::bind("0.0.0.0:0") {
+        match UdpSocket::bind(&self.settings.bind_address) {
             Ok(socket) => {
-                socket.set_nonblocking(true).expect("Failed to set non-blocking mode");
-                
-                Self::server_init_info(&socket);
-                
-                // Create a buffer for sending data
-                let mut buf = [0; 1024];
-
-                let mut server_game_logic = ServerGameLogic::new();
-                let mut time_step = Instant::now();
-            
+                if let Err(err) = socket.set_nonblocking(true) {
+                    log::error!("The socket couldn't be set as non blocking, the server can't run: {}", err);
+                    return;
+                }
+
+                Self::server_init_info(&socket, &self.map);
+                self.serve(socket);
+            },
+            Err(err) => log::error!("The binding of the ip to a udp socket was not successfull: {}", err),
+        }
+    }
+
+    /// # Host
+    /// Starts the server on a new thread, it's used when a player hosts a game from the main menu.
+    ///
+    /// ## Returns:
+    /// - The handle of the server, the server keeps running until it's dropped.
+    pub fn host(mut self) -> Result<HostedServer, String> {
+        let socket = UdpSocket::bind(&self.settings.bind_address).map_err(|err| format!("The server couldn't be started on {}: {}", self.settings.bind_address, err))?;
+        socket.set_nonblocking(true).map_err(|err| format!("The server couldn't be started: {}", err))?;
+        let address = socket.local_addr().map_err(|err| format!("The server couldn't be started: {}", err))?;
+
+        Self::server_init_info(&socket, &self.map);
+
+        let running = self.running.clone();
+        let thread = thread::spawn(move || self.serve(socket));
+
+        Ok(HostedServer { address, running, thread: Some(thread) })
+    }
+
+    fn serve(&mut self, socket: UdpSocket) {
+        // Create a buffer for sending data
+        let mut buf = [0; 65536];
+
+        let mut server_game_logic = ServerGameLogic::new(self.map.clone(), self.movement, self.teams, self.settings.combat);
+        let mut time_step = Instant::now();
+        let tick_time = Duration::from_secs_f32(1.0 / self.settings.tick_rate);
+        let mut overruns = 0;
+        let mut last_overrun_report = Instant::now() - Self::OVERRUN_REPORT;
+
+        while self.running.load(Ordering::Relaxed) {
+            if time_step.elapsed().as_secs_f32() >= (1.0 / self.settings.tick_rate) {
+                time_step = Instant::now();
+                self.tick += 1;
+                self.time.update();
+                self.drop_timed_out(&socket);
+                for connection in self.connections.values_mut() {
+                    connection.input_depth = std::mem::take(&mut connection.inputs);
+                }
+                server_game_logic.update(self);
+
+                // recieve every datagram that arrived since the last tick
                 loop {
-                    if time_step.elapsed().as_secs_f32() >= (1.0 / Server::TICK_RATE) {
-                        time_step = Instant::now();
-                        self.time.update();
-                        server_game_logic.update(self);
-
-                        // recieve data from the client (load the client to "connection" if its the first time getting data from them)
-                        match socket.recv_from(&mut buf) {
-                            Ok((amt, src)) => {
-                                // Convert the received data to a string and send a response
-                                let received = std::str::from_utf8(&buf[..amt]).expect("Invalid UTF-8 data");
-                                socket.send_to("Ok".as_bytes(), src).expect("Failed to send data");
-                        
-                                // Insert the received data into the connections HashMap
-                                match self.connections.get_mut(&src.to_string()) {
-                                    Some(existent_connection) => {
-                                        *existent_connection = received.to_string();
-                                    },
-                                    None => {
-                                        println!("The user {} has connected to the server", &src.to_string());
-                                        self.connections.insert(src.to_string(), received.to_string());
-                                    },
-                                }
-                            },
-                            Err(ref err) if err.kind() == ErrorKind::WouldBlock => {
-                                // println!("this will jump everything if there is no data");
-                            },
-                            Err(err) => {
-                                // Handle other errors
-                                println!("Error: {}", err);
-                                break;
+                    match socket.recv_from(&mut buf) {
+                        Ok((amt, src)) => {
+                            log::trace!("{} bytes from {}", amt, src);
+                            match error::decode(&buf[..amt], src) {
+                                Ok(message) => self.handle_message(&socket, message, src),
+                                Err(err) => self.invalid_message(&socket, err),
                             }
-                        }
-
-                        // update server game logic and send positions to all clients
-                        let data_to_send = serde_json::to_string(&server_game_logic.returnable).unwrap();
-
-                        if data_to_send != self.last_data_sent {
-                            // Send data to all connected clients
-                            for connection in &self.connections {
-                                socket
-                                    .send_to(
-                                        data_to_send.as_bytes(),
-                                        connection.0,
-                                    )
-                                    .expect("Failed to send data");
-                                self.last_data_sent = data_to_send.clone();
+                        },
+                        Err(ref err) if err.kind() == ErrorKind::WouldBlock => {
+                            // there is no more data for this tick
+                            break;
+                        },
+                        Err(err) => {
+                            // the socket still works after these errors, so the server keeps serving the other clients
+                            let err = NetworkError::Receive(err);
+                            if err.is_connection_reset() {
+                                log::debug!("{}, a client left without saying goodbye", err);
+                                continue;
                             }
+                            log::error!("{}, the rest of the data is read on the next tick", err);
+                            break;
                         }
-                    } 
+                    }
+                }
+
+                self.resend_chat(&socket);
+
+                // update server game logic and send positions to all clients
+                match error::encode(&ServerMessage::Snapshot(server_game_logic.returnable.clone())) {
+                    Ok(data_to_send) => self.send_snapshot(&socket, data_to_send),
+                    Err(err) => log::error!("{}, the snapshot of the tick {} is not sent", err, self.tick),
+                }
+
+                let took = time_step.elapsed();
+                if took > tick_time {
+                    overruns += 1;
+                    if last_overrun_report.elapsed() >= Self::OVERRUN_REPORT {
+                        log::warn!("{} ticks took longer than {:.1} ms, the last one took {:.1} ms", overruns, tick_time.as_secs_f32() * 1000.0, took.as_secs_f32() * 1000.0);
+                        overruns = 0;
+                        last_overrun_report = Instant::now();
+                    }
+                }
+            } else {
+                // there is nothing to do until the next tick, so we don't keep the cpu busy
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+    }
+
+    /// # Handle Message
+    /// Reads a message from a client, joins are answered with the map the server is using, inputs are saved on the connection so the game logic uses them on the next tick.
+    fn handle_message(&mut self, socket: &UdpSocket, message: ClientMessage, src: SocketAddr) {
+        if let Some(connection) = self.connections.get_mut(&src.to_string()) {
+            connection.last_message = Instant::now();
+        }
+
+        match message {
+            ClientMessage::Join { name } => {
+                if !self.connections.contains_key(&src.to_string()) && self.connections.len() >= self.settings.max_players {
+                    log::warn!("The user {} was rejected, the server is full", &src.to_string());
+                    // it's not connected, so there is nothing to drop if this fails
+                    if let Err(err) = error::send_to(socket, &ServerMessage::Rejected { reason: format!("The server is full ({} players)", self.settings.max_players) }, src) {
+                        log::warn!("{}", err);
+                    }
+                    return;
+                }
+
+                if !self.connections.contains_key(&src.to_string()) {
+                    let name = player_name::sanitize_name(&name);
+                    let name = player_name::unique_name(&name, self.connections.values().map(|connection| connection.name.as_str()));
+
+                    let id = format!("player-{}", self.next_player_id);
+                    self.next_player_id += 1;
+
+                    log::info!("The user {} has connected to the server as {}", &src.to_string(), name);
+                    self.connections.insert(src.to_string(), Connection {
+                        id,
+                        name: name.clone(),
+                        packet: None,
+                        requested_team: None,
+                        chat_in: ReliableReceiver::new(),
+                        chat_out: ReliableSender::new(),
+                        ready: false,
+                        last_message: Instant::now(),
+                        inputs: 0,
+                        input_depth: 0,
+                        invalid_messages: 0,
+                        send_failures: 0,
+                        chat_times: VecDeque::new(),
+                    });
+                    self.broadcast_chat(socket, ChatMessage { from: None, text: format!("{} joined the game", name) });
+                }
+
+                // the welcome is sent again if the client asks for it, since the first one could have been lost
+                // the join message could have made some client drop, even this one
+                if let Some(connection) = self.connections.get(&src.to_string()) {
+                    let welcome = ServerMessage::Welcome { map: self.map.clone(), name: connection.name.clone(), id: connection.id.clone() };
+                    self.send(socket, &src.to_string(), &welcome);
+                }
+            },
+            ClientMessage::Input(packet) => {
+                if let Some(connection) = self.connections.get_mut(&src.to_string()) {
+                    connection.packet = Some(packet);
+                    connection.inputs += 1;
+                }
+            },
+            ClientMessage::ChooseTeam(team) => {
+                if let Some(connection) = self.connections.get_mut(&src.to_string()) {
+                    connection.requested_team = Some(team);
+                }
+            },
+            ClientMessage::Chat { sequence, text } => {
+                // an empty text keeps its sequence, otherwise the next ones would wait for it forever
+                let (name, texts) = match self.connections.get_mut(&src.to_string()) {
+                    Some(connection) => (connection.name.clone(), connection.chat_in.receive(sequence, ChatMessage::sanitize(&text))),
+                    None => return,
+                };
+                let texts = match texts {
+                    Some(texts) => texts,
+                    None => {
+                        log::debug!("The chat message {} from {} is too far ahead, it's dropped", sequence, src);
+                        return;
+                    },
+                };
+
+                // duplicates are acknowledged too, the first acknowledge could have been lost
+                self.send(socket, &src.to_string(), &ServerMessage::ChatAck { sequence });
+
+                for text in texts.into_iter().flatten() {
+                    if self.chat_allowed(&src.to_string()) {
+                        self.broadcast_chat(socket, ChatMessage { from: Some(name.clone()), text });
+                    } else {
+                        self.send_chat(socket, &src.to_string(), ChatMessage { from: None, text: String::from("You are sending messages too fast") });
+                    }
+                }
+            },
+            ClientMessage::ChatAck { sequence } => {
+                if let Some(connection) = self.connections.get_mut(&src.to_string()) {
+                    connection.chat_out.acknowledge(sequence);
+                }
+            },
+            ClientMessage::Ready => {
+                if let Some(connection) = self.connections.get_mut(&src.to_string()) {
+                    connection.ready = true;
+                }
+            },
+            ClientMessage::KeepAlive => {},
+            ClientMessage::Ping { sequence } => {
+                if let Some(connection) = self.connections.get(&src.to_string()) {
+                    let pong = ServerMessage::Pong { sequence, tick: self.tick, input_depth: connection.input_depth };
+                    self.send(socket, &src.to_string(), &pong);
+                }
+            },
+            ClientMessage::Leave => {
+                if let Some(connection) = self.connections.remove(&src.to_string()) {
+                    log::info!("The user {} ({}) has left the server", &src.to_string(), connection.name);
+                    self.broadcast_chat(socket, ChatMessage { from: None, text: format!("{} left the game", connection.name) });
                 }
             },
-            Err(err) => eprintln!("The binding of the ip to a udp socket was not successfull: {}", err),
         }
     }
 
-    fn server_init_info(socket: &UdpSocket) {
-        match socket.local_addr() {
-            Ok(local_address) => {
-                println!("Server started at:\n - IP: {}:{}", local_address.ip(), local_address.port());
+    /// # Invalid Message
+    /// A datagram that is not a message of the protocol is ignored, but a connected client that keeps sending them is kicked.
+    fn invalid_message(&mut self, socket: &UdpSocket, err: NetworkError) {
+        log::warn!("{}", err);
+
+        let address = match err.peer() {
+            Some(address) => address.to_string(),
+            None => return,
+        };
+        let invalid_messages = match self.connections.get_mut(&address) {
+            Some(connection) => {
+                connection.invalid_messages += 1;
+                connection.invalid_messages
             },
-            Err(_) => todo!(),
+            None => return,
+        };
+
+        if invalid_messages >= Self::MAX_INVALID_MESSAGES {
+            self.kick(socket, &address, "it sent too many invalid messages");
         }
-        println!("Messages:");
-    
     }
-}
\ No newline at end of file
+
+    /// # Kick
+    /// Drops one client because of something that went wrong with it, the other clients are told on the chat and keep playing.
+    pub fn kick(&mut self, socket: &UdpSocket, address: &str, reason: &str) {
+        if let Some(connection) = self.connections.remove(address) {
+            log::warn!("The user {} ({}) was kicked, {}", address, connection.name, reason);
+            self.broadcast_chat(socket, ChatMessage { from: None, text: format!("{} lost the connection", connection.name) });
+        }
+    }
+
+    // sends a message to a connected client, if it can't be reached for a while it's kicked
+    fn send(&mut self, socket: &UdpSocket, address: &str, message: &ServerMessage) {
+        match error::send_to(socket, message, address) {
+            Ok(_) => self.send_succeeded(address),
+            Err(err) => self.send_failed(socket, address, err),
+        }
+    }
+
+    fn send_succeeded(&mut self, address: &str) {
+        if let Some(connection) = self.connections.get_mut(address) {
+            connection.send_failures = 0;
+        }
+    }
+
+    /// # Send Failed
+    /// Only the errors about the client count against it, and it's kicked once they reach [`Server::MAX_SEND_FAILURES`] in a row.
+    ///
+    /// A full buffer is skipped (the next tick sends the world again), and a message that can't be serialized or doesn't fit is a bug of the server, not of the client.
+    fn send_failed(&mut self, socket: &UdpSocket, address: &str, err: NetworkError) {
+        match err {
+            NetworkError::Send { .. } if err.is_peer_error() => {},
+            NetworkError::Send { .. } => {
+                log::debug!("{}, it's skipped", err);
+                return;
+            },
+            err => {
+                log::error!("{}", err);
+                return;
+            },
+        }
+
+        let failures = match self.connections.get_mut(address) {
+            Some(connection) => {
+                connection.send_failures += 1;
+                connection.send_failures
+            },
+            None => return,
+        };
+
+        if failures >= Self::MAX_SEND_FAILURES {
+            self.kick(socket, address, &format!("{} ({} times in a row)", err, failures));
+        } else {
+            log::debug!("{}", err);
+        }
+    }
+
+    /// Sends the snapshot to every client when it changed, or when the clients didn't get one for a while.
+    fn send_snapshot(&mut self, socket: &UdpSocket, data: String) {
+        if data == self.last_data_sent && self.last_data_sent_at.elapsed() < Self::KEEP_ALIVE {
+            return;
+        }
+
+        let mut failed = Vec::new();
+        for (address, connection) in &mut self.connections {
+            match socket.send_to(data.as_bytes(), address) {
+                Ok(_) => connection.send_failures = 0,
+                Err(source) => failed.push(NetworkError::Send { address: address.clone(), source }),
+            }
+        }
+        self.last_data_sent = data;
+        self.last_data_sent_at = Instant::now();
+
+        for err in failed {
+            if let NetworkError::Send { address, .. } = &err {
+                let address = address.clone();
+                self.send_failed(socket, &address, err);
+            }
+        }
+    }
+
+    /// Drops the clients that didn't send anything for a while, their players are removed by the game logic on the next tick.
+    fn drop_timed_out(&mut self, socket: &UdpSocket) {
+        let timed_out: Vec<String> = self.connections.iter()
+            .filter(|(_, connection)| connection.last_message.elapsed() >= Self::TIMEOUT)
+            .map(|(address, _)| address.clone())
+            .collect();
+
+        for address in timed_out {
+            self.kick(socket, &address, "it timed out");
+        }
+    }
+
+    // counts a chat message of the client if it's under the limit, the ones older than the window are forgotten
+    fn chat_allowed(&mut self, address: &str) -> bool {
+        let connection = match self.connections.get_mut(address) {
+            Some(connection) => connection,
+            None => return false,
+        };
+
+        while connection.chat_times.front().is_some_and(|time| time.elapsed() >= Self::CHAT_WINDOW) {
+            connection.chat_times.pop_front();
+        }
+        if connection.chat_times.len() >= Self::CHAT_LIMIT {
+            return false;
+        }
+
+        connection.chat_times.push_back(Instant::now());
+        true
+    }
+
+    /// # Broadcast Chat
+    /// Sends a chat message to every connected client, each one keeps receiving it until it acknowledges it.
+    ///
+    /// The clients that failed too many times (or stopped acknowledging the chat) are kicked after it, and they get their own message, so it ends once every client left got them.
+    fn broadcast_chat(&mut self, socket: &UdpSocket, message: ChatMessage) {
+        let addresses: Vec<String> = self.connections.keys().cloned().collect();
+        for address in addresses {
+            self.send_chat(socket, &address, message.clone());
+        }
+    }
+
+    // sends a chat message to one client through its reliable channel
+    fn send_chat(&mut self, socket: &UdpSocket, address: &str, message: ChatMessage) {
+        let sequence = match self.connections.get_mut(address).map(|connection| connection.chat_out.send(message.clone())) {
+            Some(Some(sequence)) => sequence,
+            Some(None) => {
+                self.kick(socket, address, &format!("it didn't acknowledge {} chat messages", ReliableSender::<ChatMessage>::MAX_PENDING));
+                return;
+            },
+            None => return,
+        };
+
+        self.send(socket, address, &ServerMessage::Chat { sequence, message });
+    }
+
+    fn resend_chat(&mut self, socket: &UdpSocket) {
+        let mut failed = Vec::new();
+        for (address, connection) in &mut self.connections {
+            for (sequence, message) in connection.chat_out.due_for_resend() {
+                log::debug!("The chat message {} was sent again to {}", sequence, address);
+                match error::send_to(socket, &ServerMessage::Chat { sequence, message }, address) {
+                    Ok(_) => connection.send_failures = 0,
+                    Err(err) => {
+                        failed.push((address.clone(), err));
+                        break;
+                    },
+                }
+            }
+        }
+
+        for (address, err) in failed {
+            self.send_failed(socket, &address, err);
+        }
+    }
+
+    fn server_init_info(socket: &UdpSocket, map: &MapData) {
+        match socket.local_addr() {
+            Ok(local_address) => log::info!("Server started at {}:{}", local_address.ip(), local_address.port()),
+            Err(err) => log::warn!("Server started, but its address couldn't be read: {}", err),
+        }
+        log::info!("Map: {} ({} walls, {} spawn points)", map.name, map.walls.len(), map.spawn_points.len());
+    }
+}
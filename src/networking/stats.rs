@@ -0,0 +1,247 @@
+use std::{collections::VecDeque, time::{Duration, Instant}};
+
+/// # History
+/// The last values of a counter, the oldest is dropped when a new one doesn't fit. The debug overlay draws them as a graph.
+pub struct History {
+    values: VecDeque<f32>,
+    capacity: usize,
+}
+
+impl History {
+    pub fn new(capacity: usize) -> Self {
+        Self { values: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    pub fn push(&mut self, value: f32) {
+        if self.values.len() == self.capacity {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    /// Values from the oldest to the newest.
+    pub fn values(&self) -> impl Iterator<Item = f32> + '_ {
+        self.values.iter().copied()
+    }
+
+    pub fn max(&self) -> f32 {
+        self.values().fold(0.0, f32::max)
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+// a value that is counted during a second and then saved as the rate of that second
+struct Rate {
+    current: u64,
+    last: u64,
+    history: History,
+}
+
+impl Rate {
+    fn new() -> Self {
+        Self { current: 0, last: 0, history: History::new(NetStats::HISTORY) }
+    }
+
+    fn add(&mut self, amount: u64) {
+        self.current += amount;
+    }
+
+    fn roll(&mut self) {
+        self.last = self.current;
+        self.current = 0;
+        self.history.push(self.last as f32);
+    }
+}
+
+/// # Net Stats
+/// Counters of the connection with the server, the app updates them while sending and receiving and the debug overlay shows them.
+///
+/// The round trip time comes from `Ping`s the client sends each [`NetStats::PING_INTERVAL`], a ping without answer after [`NetStats::PING_TIMEOUT`] is counted as lost.
+pub struct NetStats {
+    bytes_in: Rate,
+    bytes_out: Rate,
+    snapshots: Rate,
+    // sequence and send time of the pings without answer
+    pending_pings: VecDeque<(u32, Instant)>,
+    next_ping: u32,
+    last_ping: Instant,
+    // if each of the last pings was answered, the loss is the part that wasn't
+    ping_results: VecDeque<bool>,
+    rtt: Option<Duration>,
+    jitter: f32,
+    rtt_history: History,
+    server_tick: u64,
+    input_depth: u32,
+    last_snapshot: Option<Instant>,
+    last_roll: Instant,
+}
+
+impl NetStats {
+    pub const PING_INTERVAL: Duration = Duration::from_millis(500);
+    pub const PING_TIMEOUT: Duration = Duration::from_secs(2);
+    /// Values kept on each graph, one per second for the rates and one per ping for the round trip time.
+    pub const HISTORY: usize = 60;
+    // pings used to calculate the loss
+    const LOSS_WINDOW: usize = 20;
+
+    pub fn new() -> Self {
+        Self {
+            bytes_in: Rate::new(),
+            bytes_out: Rate::new(),
+            snapshots: Rate::new(),
+            pending_pings: VecDeque::new(),
+            next_ping: 0,
+            last_ping: Instant::now(),
+            ping_results: VecDeque::with_capacity(Self::LOSS_WINDOW),
+            rtt: None,
+            jitter: 0.0,
+            rtt_history: History::new(Self::HISTORY),
+            server_tick: 0,
+            input_depth: 0,
+            last_snapshot: None,
+            last_roll: Instant::now(),
+        }
+    }
+
+    /// # Update
+    /// Saves the rates of the second that ended and counts the pings that took too long as lost, it's called once per frame.
+    pub fn update(&mut self) {
+        if self.last_roll.elapsed() >= Duration::from_secs(1) {
+            self.last_roll = Instant::now();
+            self.bytes_in.roll();
+            self.bytes_out.roll();
+            self.snapshots.roll();
+        }
+
+        while let Some((_, sent)) = self.pending_pings.front() {
+            if sent.elapsed() < Self::PING_TIMEOUT {
+                break;
+            }
+            self.pending_pings.pop_front();
+            self.push_ping_result(false);
+        }
+    }
+
+    pub fn sent(&mut self, bytes: usize) {
+        self.bytes_out.add(bytes as u64);
+    }
+
+    pub fn received(&mut self, bytes: usize) {
+        self.bytes_in.add(bytes as u64);
+    }
+
+    pub fn snapshot(&mut self) {
+        self.snapshots.add(1);
+        self.last_snapshot = Some(Instant::now());
+    }
+
+    /// Returns the sequence of a new ping when it's time to send one.
+    pub fn ping_due(&mut self) -> Option<u32> {
+        if self.last_ping.elapsed() < Self::PING_INTERVAL {
+            return None;
+        }
+
+        let sequence = self.next_ping;
+        self.next_ping = self.next_ping.wrapping_add(1);
+        self.last_ping = Instant::now();
+        self.pending_pings.push_back((sequence, self.last_ping));
+        Some(sequence)
+    }
+
+    /// # Pong
+    /// The server answered a ping, the round trip time is the time since it was sent.
+    ///
+    /// The jitter is the average change between two round trip times, smoothed the same way RTP does (each new change weights 1/16).
+    pub fn pong(&mut self, sequence: u32, tick: u64, input_depth: u32) {
+        self.server_tick = self.server_tick.max(tick);
+        self.input_depth = input_depth;
+
+        // an answer that arrives after the timeout was already counted as lost
+        let pending = self.pending_pings.iter().position(|(pending, _)| *pending == sequence).and_then(|index| self.pending_pings.remove(index));
+        let rtt = match pending {
+            Some((_, sent)) => sent.elapsed(),
+            None => return,
+        };
+
+        if let Some(last) = self.rtt {
+            let change = (rtt.as_secs_f32() - last.as_secs_f32()).abs() * 1000.0;
+            self.jitter += (change - self.jitter) / 16.0;
+        }
+        self.rtt = Some(rtt);
+        self.rtt_history.push(rtt.as_secs_f32() * 1000.0);
+        self.push_ping_result(true);
+    }
+
+    fn push_ping_result(&mut self, answered: bool) {
+        if self.ping_results.len() == Self::LOSS_WINDOW {
+            self.ping_results.pop_front();
+        }
+        self.ping_results.push_back(answered);
+    }
+
+    /// Round trip time of the last ping in milliseconds, `None` until the first answer.
+    pub fn rtt_ms(&self) -> Option<f32> {
+        self.rtt.map(|rtt| rtt.as_secs_f32() * 1000.0)
+    }
+
+    pub fn jitter_ms(&self) -> f32 {
+        self.jitter
+    }
+
+    /// Part of the last pings that didn't get an answer, from 0 to 1.
+    pub fn loss(&self) -> f32 {
+        if self.ping_results.is_empty() {
+            return 0.0;
+        }
+        self.ping_results.iter().filter(|answered| !**answered).count() as f32 / self.ping_results.len() as f32
+    }
+
+    /// Bytes received on the last second.
+    pub fn bytes_in(&self) -> u64 {
+        self.bytes_in.last
+    }
+
+    /// Bytes sent on the last second.
+    pub fn bytes_out(&self) -> u64 {
+        self.bytes_out.last
+    }
+
+    /// Snapshots received on the last second.
+    pub fn snapshot_rate(&self) -> u64 {
+        self.snapshots.last
+    }
+
+    pub fn server_tick(&self) -> u64 {
+        self.server_tick
+    }
+
+    /// Inputs of this client the server got on its last tick, more than 1 means some were replaced before being used.
+    pub fn input_depth(&self) -> u32 {
+        self.input_depth
+    }
+
+    /// # Interpolation Delay
+    /// How old the world that is drawn is, the client draws the last snapshot as it arrives so the delay is the age of that snapshot.
+    pub fn interpolation_delay_ms(&self) -> Option<f32> {
+        self.last_snapshot.map(|received| received.elapsed().as_secs_f32() * 1000.0)
+    }
+
+    pub fn rtt_history(&self) -> &History {
+        &self.rtt_history
+    }
+
+    pub fn bytes_in_history(&self) -> &History {
+        &self.bytes_in.history
+    }
+
+    pub fn bytes_out_history(&self) -> &History {
+        &self.bytes_out.history
+    }
+
+    pub fn snapshot_history(&self) -> &History {
+        &self.snapshots.history
+    }
+}
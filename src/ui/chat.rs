@@ -0,0 +1,153 @@
+use std::{collections::VecDeque, time::{Duration, Instant}};
+
+use sdl2::{clipboard::ClipboardUtil, event::Event, keyboard::Keycode, pixels::Color, rect::{Point, Rect}, render::{Canvas, TextureQuery}, ttf::Font, video::Window};
+
+use crate::{game_object::GameObject, input::text_input::TextInput, networking::protocol::ChatMessage};
+
+use super::text_cache::{TextCache, TextQuality};
+
+struct ChatEntry {
+    message: ChatMessage,
+    received: Instant,
+}
+
+/// # Chat Box
+/// On-screen chat, it has the log of the last messages (they fade out after a while) and the input line used to write new ones.
+///
+/// The `game_object` is the bottom left corner of the chat, the log grows upwards from there.
+pub struct ChatBox {
+    pub game_object: GameObject,
+    pub open: bool,
+    pub input: TextInput,
+    entries: VecDeque<ChatEntry>,
+    // the key that opened the chat can also type a character, it comes right after it and is ignored
+    skip_text: bool,
+}
+
+impl ChatBox {
+    const MAX_ENTRIES: usize = 8;
+    const LINE_HEIGHT: i32 = 24;
+    // time a message stays on screen, the last part of it is used to fade it out
+    const VISIBLE_TIME: Duration = Duration::from_secs(8);
+    const FADE_TIME: Duration = Duration::from_secs(2);
+
+    pub fn new(game_object: GameObject) -> Self {
+        let mut input = TextInput::new(Self::input_line(&game_object), "", "Say something...", ChatMessage::MAX_LENGTH);
+        input.color = Color::RGBA(0, 0, 0, 160);
+
+        Self {
+            game_object,
+            open: false,
+            input,
+            entries: VecDeque::new(),
+            skip_text: false,
+        }
+    }
+
+    /// Moves the chat, used when the window changes its size.
+    pub fn place(&mut self, game_object: GameObject) {
+        self.game_object = game_object;
+        self.input.game_object = Self::input_line(&game_object);
+    }
+
+    // the input line goes right above the bottom left corner
+    fn input_line(game_object: &GameObject) -> GameObject {
+        GameObject { active: true, x: game_object.x, y: game_object.y - Self::LINE_HEIGHT as f32, width: game_object.width, height: Self::LINE_HEIGHT as f32 }
+    }
+
+    pub fn push(&mut self, message: ChatMessage) {
+        self.entries.push_back(ChatEntry { message, received: Instant::now() });
+
+        while self.entries.len() > Self::MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Opens the input line, it's called by the gameplay when a key of the chat action is pressed.
+    pub fn open_input(&mut self) {
+        self.open = true;
+        self.skip_text = true;
+        self.input.set_text("");
+        self.input.focus();
+    }
+
+    /// # Handle Event
+    /// While the chat is open the text typed goes to the input line, return sends it and escape closes it without sending.
+    ///
+    /// ## Returns:
+    /// - The text to send when the player presses return with something written.
+    pub fn handle_event(&mut self, event: &Event, clipboard: &ClipboardUtil) -> Option<String> {
+        if std::mem::take(&mut self.skip_text) {
+            if let Event::TextInput { .. } = event {
+                return None;
+            }
+        }
+
+        match event {
+            Event::KeyDown { keycode: Some(Keycode::Return), .. } | Event::KeyDown { keycode: Some(Keycode::KpEnter), .. } if self.open => {
+                self.open = false;
+                self.input.unfocus();
+                let text = std::mem::take(&mut self.input.text);
+                self.input.set_text("");
+                return ChatMessage::sanitize(&text);
+            },
+            Event::KeyDown { keycode: Some(Keycode::Escape), .. } if self.open => {
+                self.open = false;
+                self.input.unfocus();
+                self.input.set_text("");
+            },
+            // clicks don't close the chat, only the keyboard goes to the input line
+            Event::MouseButtonDown { .. } => {},
+            _ if self.open => {
+                self.input.handle_event(event, clipboard);
+            },
+            _ => {}
+        }
+
+        None
+    }
+
+    pub fn render(&self, canvas: &mut Canvas<Window>, text_cache: &mut TextCache, font: &Font) {
+        let x = self.game_object.x as i32;
+        let mut y = self.game_object.y as i32 - Self::LINE_HEIGHT;
+
+        if self.open {
+            self.input.render(canvas, text_cache, font);
+        }
+        y -= Self::LINE_HEIGHT;
+
+        for entry in self.entries.iter().rev() {
+            let elapsed = entry.received.elapsed();
+
+            // while the chat is open the whole log is shown
+            let alpha = if self.open || elapsed + Self::FADE_TIME <= Self::VISIBLE_TIME {
+                255
+            } else if elapsed >= Self::VISIBLE_TIME {
+                0
+            } else {
+                let left = (Self::VISIBLE_TIME - elapsed).as_secs_f32() / Self::FADE_TIME.as_secs_f32();
+                (left * 255.0) as u8
+            };
+
+            if alpha > 0 {
+                let (text, color) = match &entry.message.from {
+                    Some(name) => (format!("{}: {}", name, entry.message.text), Color::RGBA(255, 255, 255, alpha)),
+                    None => (entry.message.text.clone(), Color::RGBA(230, 200, 80, alpha)),
+                };
+                Self::render_line(canvas, text_cache, font, &text, color, Point::new(x + 4, y));
+            }
+            y -= Self::LINE_HEIGHT;
+        }
+    }
+
+    // the alpha of the color fades the line, the text is cached without it so a fading line doesn't create a texture per frame
+    fn render_line(canvas: &mut Canvas<Window>, text_cache: &mut TextCache, font: &Font, text: &str, color: Color, position: Point) {
+        if let Some(texture) = text_cache.get(font, text, Color::RGB(color.r, color.g, color.b), TextQuality::Blended, None) {
+            // the texture is shared by the cache, so the alpha is put back once it's drawn
+            texture.set_alpha_mod(color.a);
+            let TextureQuery { width, height, .. } = texture.query();
+            canvas.copy(texture, None, Rect::new(position.x(), position.y(), width, height)).unwrap();
+            texture.set_alpha_mod(255);
+        }
+    }
+}
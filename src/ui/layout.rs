@@ -0,0 +1,158 @@
+use crate::game_object::GameObject;
+
+/// Point of the window (and of the element) used to place an element.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Center,
+    BottomLeft,
+}
+
+impl Anchor {
+    // position of the anchor from 0 (left/top) to 1 (right/bottom)
+    fn factors(&self) -> (f32, f32) {
+        match self {
+            Anchor::TopLeft => (0.0, 0.0),
+            Anchor::Top => (0.5, 0.0),
+            Anchor::TopRight => (1.0, 0.0),
+            Anchor::Center => (0.5, 0.5),
+            Anchor::BottomLeft => (0.0, 1.0),
+        }
+    }
+}
+
+/// A size or distance, in pixels or as a percentage (from 0 to 100) of the window.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Length {
+    Pixels(f32),
+    Percent(f32),
+}
+
+impl Length {
+    pub fn resolve(&self, window: f32) -> f32 {
+        match self {
+            Length::Pixels(pixels) => *pixels,
+            Length::Percent(percent) => window * percent / 100.0,
+        }
+    }
+}
+
+/// # Layout
+/// Describes where an element goes relative to the window, so it can be placed again when the window changes its size.
+///
+/// The `anchor` is used both on the window and on the element, for example `TopRight` puts the top right corner of the element on the top right corner of the window.
+/// The offset moves the element from there (positive values go right and down) and the `padding` keeps it away from the edges it's anchored to.
+#[derive(Clone, Copy)]
+pub struct Layout {
+    pub anchor: Anchor,
+    pub offset_x: Length,
+    pub offset_y: Length,
+    pub width: Length,
+    pub height: Length,
+    pub padding: f32,
+}
+
+impl Layout {
+    pub fn new(anchor: Anchor, width: Length, height: Length) -> Self {
+        Self {
+            anchor,
+            offset_x: Length::Pixels(0.0),
+            offset_y: Length::Pixels(0.0),
+            width,
+            height,
+            padding: 0.0,
+        }
+    }
+
+    /// Layout with the size in pixels, the most common one.
+    pub fn pixels(anchor: Anchor, width: f32, height: f32) -> Self {
+        Self::new(anchor, Length::Pixels(width), Length::Pixels(height))
+    }
+
+    pub fn offset(mut self, x: f32, y: f32) -> Self {
+        self.offset_x = Length::Pixels(x);
+        self.offset_y = Length::Pixels(y);
+        self
+    }
+
+    pub fn padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// # Resolve
+    /// Calculates the rectangle of the element on a window of the given size.
+    ///
+    /// ## Returns:
+    /// - An active `GameObject` with the position and size of the element.
+    pub fn resolve(&self, window: (u32, u32)) -> GameObject {
+        let (window_width, window_height) = (window.0 as f32, window.1 as f32);
+        let (factor_x, factor_y) = self.anchor.factors();
+
+        let width = self.width.resolve(window_width);
+        let height = self.height.resolve(window_height);
+
+        // the padding pushes the element away from the edge it's anchored to, centered axes don't use it
+        let padding_x = self.padding * (1.0 - 2.0 * factor_x);
+        let padding_y = self.padding * (1.0 - 2.0 * factor_y);
+
+        GameObject {
+            active: true,
+            x: window_width * factor_x - width * factor_x + padding_x + self.offset_x.resolve(window_width),
+            y: window_height * factor_y - height * factor_y + padding_y + self.offset_y.resolve(window_height),
+            width,
+            height,
+        }
+    }
+}
+
+/// # Stack
+/// Places elements one under another, the first one uses the `layout` and the next ones are moved down by its height plus the `spacing`.
+#[derive(Clone, Copy)]
+pub struct Stack {
+    pub layout: Layout,
+    pub spacing: f32,
+}
+
+impl Stack {
+    pub fn new(layout: Layout, spacing: f32) -> Self {
+        Self { layout, spacing }
+    }
+
+    /// The place of the element on `index` position of the stack.
+    pub fn item(&self, index: usize) -> StackItem {
+        StackItem { stack: *self, index }
+    }
+
+    pub fn resolve(&self, index: usize, window: (u32, u32)) -> GameObject {
+        let mut game_object = self.layout.resolve(window);
+        game_object.y += (game_object.height + self.spacing) * index as f32;
+        game_object
+    }
+}
+
+/// One element of a [`Stack`].
+#[derive(Clone, Copy)]
+pub struct StackItem {
+    stack: Stack,
+    index: usize,
+}
+
+/// Anything that can give the place of an element on a window of a given size.
+pub trait Place {
+    fn place(&self, window: (u32, u32)) -> GameObject;
+}
+
+impl Place for Layout {
+    fn place(&self, window: (u32, u32)) -> GameObject {
+        self.resolve(window)
+    }
+}
+
+impl Place for StackItem {
+    fn place(&self, window: (u32, u32)) -> GameObject {
+        self.stack.resolve(self.index, window)
+    }
+}
@@ -0,0 +1,175 @@
+use sdl2::{event::Event, keyboard::{Keycode, Mod}, render::Canvas, ttf::Font, video::Window};
+
+use crate::input::button_module::Button;
+
+use super::{layout::Place, text_cache::TextCache};
+
+struct Widget<A> {
+    button: Button,
+    // labels don't have an action, so they can't be clicked or focused
+    action: Option<A>,
+    place: Box<dyn Place>,
+}
+
+/// # UI Manager
+/// Owns the buttons of a screen, sends them the SDL events, keeps track of the one hovered or focused and returns the action of the one activated.
+/// Every button has a place on the window, they are moved when the window changes its size.
+///
+/// The action can be any `Copy` value, usually an enum of the screen (or a function to call).
+///
+/// ## Keyboard:
+/// - `Down`/`Tab` and `Up`/`Shift + Tab` move the focus between the buttons.
+/// - `Return`/`Space` activate the focused button.
+pub struct UiManager<A: Copy> {
+    widgets: Vec<Widget<A>>,
+    focused: Option<usize>,
+    // size of the window the last time the widgets were placed
+    window: Option<(u32, u32)>,
+}
+
+impl<A: Copy> UiManager<A> {
+    pub fn new() -> Self {
+        Self {
+            widgets: Vec::new(),
+            focused: None,
+            window: None,
+        }
+    }
+
+    /// Adds a button that returns `action` when it's clicked, the returned id is used to get it back.
+    ///
+    /// The `place` decides where it goes on the window, see [`UiManager::layout`].
+    pub fn add_button(&mut self, place: impl Place + 'static, button: Button, action: A) -> usize {
+        self.widgets.push(Widget { button, action: Some(action), place: Box::new(place) });
+        self.window = None;
+        self.widgets.len() - 1
+    }
+
+    /// Adds a button that only shows text, it's never clicked or focused.
+    pub fn add_label(&mut self, place: impl Place + 'static, button: Button) -> usize {
+        self.widgets.push(Widget { button, action: None, place: Box::new(place) });
+        self.window = None;
+        self.widgets.len() - 1
+    }
+
+    pub fn get_mut(&mut self, id: usize) -> &mut Button {
+        &mut self.widgets[id].button
+    }
+
+    /// # Layout
+    /// Places the widgets again if the window changed its size since the last call, the screens call it every frame.
+    ///
+    /// ## Returns:
+    /// - `true` if the widgets were moved, so the screen can move the things it places by itself.
+    pub fn layout(&mut self, window: (u32, u32)) -> bool {
+        if self.window == Some(window) {
+            return false;
+        }
+
+        for widget in &mut self.widgets {
+            // the active flag belongs to the screen, the layout only moves the widget
+            let active = widget.button.game_object.active;
+            widget.button.game_object = widget.place.place(window);
+            widget.button.game_object.active = active;
+        }
+
+        self.window = Some(window);
+        true
+    }
+
+    /// Removes the focus, used when the screen is left so it isn't focused when coming back.
+    pub fn clear_focus(&mut self) {
+        self.set_focus(None);
+    }
+
+    /// # Handle Event
+    /// Sends the event to the buttons and moves the focus with the keyboard.
+    ///
+    /// ## Returns:
+    /// - The action of the button clicked (or activated with the keyboard) with this event.
+    pub fn handle_event(&mut self, event: &Event) -> Option<A> {
+        match event {
+            Event::KeyDown { keycode: Some(key), keymod, .. } => {
+                let shift = keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD);
+
+                match *key {
+                    Keycode::Down => self.move_focus(1),
+                    Keycode::Up => self.move_focus(-1),
+                    Keycode::Tab => self.move_focus(if shift { -1 } else { 1 }),
+                    Keycode::Return | Keycode::KpEnter | Keycode::Space => {
+                        return self.focused.and_then(|focused| self.widgets[focused].action);
+                    },
+                    _ => {}
+                }
+                None
+            },
+            Event::MouseMotion { .. } | Event::MouseButtonDown { .. } => {
+                let mut clicked = None;
+                let mut hovered = None;
+
+                for (id, widget) in self.widgets.iter_mut().enumerate() {
+                    if widget.action.is_none() {
+                        continue;
+                    }
+
+                    if widget.button.on_click(event) {
+                        clicked = widget.action;
+                    }
+                    if widget.button.hover {
+                        hovered = Some(id);
+                    }
+                }
+
+                // the mouse takes the focus from the keyboard while it's moving
+                if let Event::MouseMotion { .. } = event {
+                    self.set_focus(hovered);
+                } else {
+                    self.set_focus(self.focused);
+                }
+                clicked
+            },
+            _ => None,
+        }
+    }
+
+    pub fn render(&self, canvas: &mut Canvas<Window>, text_cache: &mut TextCache, font: &Font) {
+        for widget in &self.widgets {
+            widget.button.render(canvas, text_cache, font);
+        }
+    }
+
+    // moves the focus to the next (or previous) active button, going around at the ends
+    fn move_focus(&mut self, step: i32) {
+        let focusable: Vec<usize> = self.widgets.iter().enumerate()
+            .filter(|(_, widget)| widget.action.is_some() && widget.button.game_object.active)
+            .map(|(id, _)| id)
+            .collect();
+
+        if focusable.is_empty() {
+            return;
+        }
+
+        let next = match self.focused.and_then(|focused| focusable.iter().position(|id| *id == focused)) {
+            Some(position) => (position as i32 + step).rem_euclid(focusable.len() as i32) as usize,
+            None if step > 0 => 0,
+            None => focusable.len() - 1,
+        };
+
+        self.set_focus(Some(focusable[next]));
+    }
+
+    fn set_focus(&mut self, focused: Option<usize>) {
+        if let Some(previous) = self.focused {
+            if let Some(widget) = self.widgets.get_mut(previous) {
+                widget.button.color = widget.button.base_color;
+            }
+        }
+
+        self.focused = focused;
+
+        if let Some(focused) = focused {
+            let button = &mut self.widgets[focused].button;
+            button.color = button.hover_color;
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use sdl2::{pixels::Color, rect::Rect, render::Canvas, ttf::Font, video::Window};
+
+use crate::{game_object::GameObject, input::button_module::{Button, TextAlign}, networking::stats::{History, NetStats}};
+
+use super::{layout::{Anchor, Layout}, text_cache::TextCache};
+
+/// # Net Overlay
+/// Debug panel on the top right corner of the match with the counters of the connection, and small graphs of how they changed on the last minute.
+pub struct NetOverlay {
+    pub visible: bool,
+}
+
+impl NetOverlay {
+    const WIDTH: f32 = 280.0;
+    const PADDING: f32 = 10.0;
+    const LINE_HEIGHT: f32 = 20.0;
+    const GRAPH_HEIGHT: f32 = 26.0;
+    const GRAPH_SPACING: f32 = 6.0;
+    // rows of text before the graphs
+    const LINES: usize = 8;
+    const GRAPHS: usize = 4;
+
+    pub fn new() -> Self {
+        Self { visible: false }
+    }
+
+    pub fn render(&self, stats: &NetStats, canvas: &mut Canvas<Window>, text_cache: &mut TextCache, font: &Font) {
+        if !self.visible {
+            return;
+        }
+
+        let height = Self::PADDING * 2.0 + Self::LINE_HEIGHT * Self::LINES as f32 + (Self::GRAPH_HEIGHT + Self::GRAPH_SPACING) * Self::GRAPHS as f32;
+        let panel = Layout::pixels(Anchor::TopRight, Self::WIDTH, height).padding(Self::PADDING).resolve(canvas.output_size().unwrap_or((1280, 720)));
+        let (x, y) = (panel.x, panel.y);
+
+        canvas.set_draw_color(Color::RGBA(0, 0, 0, 180));
+        canvas.fill_rect(Rect::new(x as i32, y as i32, Self::WIDTH as u32, height as u32)).unwrap();
+
+        let milliseconds = |value: Option<f32>| value.map(|value| format!("{:.0} ms", value)).unwrap_or_else(|| String::from("-"));
+        let lines = [
+            format!("RTT: {}", milliseconds(stats.rtt_ms())),
+            format!("Jitter: {:.1} ms", stats.jitter_ms()),
+            format!("Loss: {:.0}%", stats.loss() * 100.0),
+            format!("In: {}  Out: {}", Self::bytes_text(stats.bytes_in()), Self::bytes_text(stats.bytes_out())),
+            format!("Snapshots: {}/s", stats.snapshot_rate()),
+            format!("Input buffer: {}", stats.input_depth()),
+            format!("Interp delay: {}", milliseconds(stats.interpolation_delay_ms())),
+            format!("Server tick: {}", stats.server_tick()),
+        ];
+
+        let left = x + Self::PADDING;
+        let mut top = y + Self::PADDING;
+        for line in lines {
+            Self::text(line, left, top, Color::WHITE).render(canvas, text_cache, font);
+            top += Self::LINE_HEIGHT;
+        }
+
+        let graphs = [
+            ("RTT", stats.rtt_history(), Color::RGB(230, 200, 80)),
+            ("In", stats.bytes_in_history(), Color::RGB(80, 160, 230)),
+            ("Out", stats.bytes_out_history(), Color::RGB(230, 120, 80)),
+            ("Snapshots", stats.snapshot_history(), Color::RGB(80, 200, 120)),
+        ];
+        for (name, history, color) in graphs {
+            top += Self::GRAPH_SPACING;
+            let area = Rect::new(left as i32, top as i32, (Self::WIDTH - Self::PADDING * 2.0) as u32, Self::GRAPH_HEIGHT as u32);
+            Self::graph(canvas, area, history, color);
+            Self::text(String::from(name), left + 2.0, top, Color::RGBA(255, 255, 255, 200)).render(canvas, text_cache, font);
+            top += Self::GRAPH_HEIGHT;
+        }
+    }
+
+    fn text(text: String, x: f32, y: f32, color: Color) -> Button {
+        Button::new(GameObject { active: true, x, y, width: 0.0, height: 0.0 }, Some(text), Color::RGBA(0, 0, 0, 0), color, Color::RGBA(0, 0, 0, 0), Color::RGBA(0, 0, 0, 0), None, TextAlign::Left)
+    }
+
+    fn bytes_text(bytes: u64) -> String {
+        if bytes >= 1024 {
+            format!("{:.1} KB/s", bytes as f32 / 1024.0)
+        } else {
+            format!("{} B/s", bytes)
+        }
+    }
+
+    // bars from the oldest value on the left to the newest on the right, the highest value fills the area
+    fn graph(canvas: &mut Canvas<Window>, area: Rect, history: &History, color: Color) {
+        canvas.set_draw_color(Color::RGBA(255, 255, 255, 25));
+        canvas.fill_rect(area).unwrap();
+
+        let max = history.max();
+        if max <= 0.0 {
+            return;
+        }
+
+        let bar_width = area.width() as f32 / history.capacity() as f32;
+        let offset = history.capacity() - history.values().count();
+        canvas.set_draw_color(color);
+        for (index, value) in history.values().enumerate() {
+            let bar_height = ((value / max) * area.height() as f32).round().max(1.0) as u32;
+            let bar_x = area.x() + ((offset + index) as f32 * bar_width) as i32;
+            canvas.fill_rect(Rect::new(bar_x, area.bottom() - bar_height as i32, bar_width.ceil().max(1.0) as u32, bar_height)).unwrap();
+        }
+    }
+}
@@ -1,9 +1,11 @@
-use sdl2::{render::{Canvas, TextureCreator, TextureQuery}, ttf::Font, video::{Window, WindowContext}, mouse::MouseButton};
+use sdl2::{render::{Canvas, TextureQuery}, ttf::Font, video::Window, mouse::MouseButton};
 use sdl2::pixels::Color;
 use sdl2::rect::Rect;
 
 use crate::game_object::GameObject;
 
+use super::text_cache::{TextCache, TextQuality};
+
 #[derive(Clone)]
 
 pub struct Label {
@@ -11,6 +13,9 @@ pub struct Label {
     pub text: String,
     pub color: Color,
     pub text_color: Color,
+    pub text_quality: TextQuality,
+    /// Splits the text in lines as wide as the label.
+    pub wrap: bool,
 }
 
 impl Label {
@@ -20,25 +25,27 @@ impl Label {
             text: text,
             color,
             text_color, 
+            text_quality: TextQuality::Solid,
+            wrap: false,
         }
     }
 
-    pub fn render(&self, canvas: &mut Canvas<Window>, texture_creator: &TextureCreator<WindowContext>, font: &Font) {
+    pub fn render(&self, canvas: &mut Canvas<Window>, text_cache: &mut TextCache, font: &Font) {
         if self.game_object.active == true {
             canvas.set_draw_color(self.color); // it must be a Color::RGB() or other
             canvas.fill_rect(Rect::new(self.game_object.x as i32, self.game_object.y as i32, self.game_object.width as u32, self.game_object.height as u32)).unwrap();
 
-            // Render the button text
-                    let surface = font.render(&self.text).solid(self.text_color).expect("Something went wrong while creating the surface");
-                    let texture = texture_creator.create_texture_from_surface(&surface).expect("Something went wrong while creating the texture");
-        
-                    // We center the text on the button
-                    let TextureQuery { width: text_width, height: text_height, .. } = texture.query();
-                    let text_x = self.game_object.x as i32 + (self.game_object.width as i32 - text_width as i32) / 2;
-                    let text_y = self.game_object.y as i32 + (self.game_object.height as i32 - text_height as i32) / 2;
-        
-                    // render
-                    canvas.copy(&texture, None, Rect::new(text_x, text_y, text_width, text_height)).unwrap();
+            // Render the label text, the texture is reused until the text changes
+            let wrap_width = if self.wrap { Some(self.game_object.width as u32) } else { None };
+            if let Some(texture) = text_cache.get(font, &self.text, self.text_color, self.text_quality, wrap_width) {
+                // We center the text on the label
+                let TextureQuery { width: text_width, height: text_height, .. } = texture.query();
+                let text_x = self.game_object.x as i32 + (self.game_object.width as i32 - text_width as i32) / 2;
+                let text_y = self.game_object.y as i32 + (self.game_object.height as i32 - text_height as i32) / 2;
+
+                // render
+                canvas.copy(texture, None, Rect::new(text_x, text_y, text_width, text_height)).unwrap();
+            }
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,101 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use sdl2::{pixels::Color, render::{Texture, TextureCreator}, ttf::Font, video::WindowContext};
+
+/// How the text is rasterized, `Solid` is the fastest and `Blended` is anti-aliased.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextQuality {
+    Solid,
+    Blended,
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct TextKey {
+    text: String,
+    // every loaded size of a font is a different TTF_Font, the height is kept too in case a pointer is reused
+    font: usize,
+    size: i32,
+    color: (u8, u8, u8, u8),
+    quality: TextQuality,
+    wrap_width: Option<u32>,
+}
+
+struct CachedText<'a> {
+    texture: Texture<'a>,
+    last_used: u64,
+}
+
+/// # Text Cache
+/// Keeps the textures of the texts already rendered, so a text is only rasterized again when it changes.
+///
+/// The textures are keyed by text, font, size, color and quality, the ones not used for a while are dropped on [`TextCache::end_frame`].
+pub struct TextCache<'a> {
+    texture_creator: &'a TextureCreator<WindowContext>,
+    entries: HashMap<TextKey, CachedText<'a>>,
+    frame: u64,
+}
+
+impl<'a> TextCache<'a> {
+    // frames a text can go without being drawn before its texture is dropped
+    const UNUSED_FRAMES: u64 = 120;
+
+    pub fn new(texture_creator: &'a TextureCreator<WindowContext>) -> Self {
+        Self {
+            texture_creator,
+            entries: HashMap::new(),
+            frame: 0,
+        }
+    }
+
+    /// # Get
+    /// Returns the texture of the text, it's created the first time the text is asked for.
+    ///
+    /// ## Params:
+    /// - wrap_width: If it's set the text is split in lines no wider than it (wrapped texts are always blended) and `\n` starts a new line.
+    ///
+    /// ## Returns:
+    /// - `None` if the text is empty or SDL couldn't render it.
+    pub fn get(&mut self, font: &Font, text: &str, color: Color, quality: TextQuality, wrap_width: Option<u32>) -> Option<&mut Texture<'a>> {
+        // SDL_ttf can't render an empty text
+        if text.is_empty() {
+            return None;
+        }
+
+        let key = TextKey {
+            text: text.to_string(),
+            font: font.raw() as usize,
+            size: font.height(),
+            color: (color.r, color.g, color.b, color.a),
+            quality,
+            wrap_width,
+        };
+        let texture_creator = self.texture_creator;
+        let frame = self.frame;
+
+        match self.entries.entry(key) {
+            Entry::Occupied(entry) => {
+                let cached = entry.into_mut();
+                cached.last_used = frame;
+                Some(&mut cached.texture)
+            },
+            Entry::Vacant(entry) => {
+                let surface = match (wrap_width, quality) {
+                    (Some(width), _) => font.render(text).blended_wrapped(color, width),
+                    (None, TextQuality::Solid) => font.render(text).solid(color),
+                    (None, TextQuality::Blended) => font.render(text).blended(color),
+                };
+                let texture = texture_creator.create_texture_from_surface(&surface.ok()?).ok()?;
+
+                Some(&mut entry.insert(CachedText { texture, last_used: frame }).texture)
+            },
+        }
+    }
+
+    /// Called once per frame after rendering, it drops the textures of the texts that are not shown anymore.
+    pub fn end_frame(&mut self) {
+        self.frame += 1;
+
+        let frame = self.frame;
+        self.entries.retain(|_, cached| cached.last_used + Self::UNUSED_FRAMES >= frame);
+    }
+}